@@ -139,3 +139,33 @@ impl Game {
         Ok(Game::new(new_board, new_pieces, new_constraints))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_model::{Direction, Pips};
+    use crate::solver;
+
+    fn game_with_uncoverable_constraint() -> Game {
+        let board = Board::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect());
+        let piece = Piece::new(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        // (5, 5) is not on the board, so no placement can ever consume this constraint.
+        let constraint = Constraint::exactly(3, [Point::new(5, 5)].into_iter().collect()).unwrap();
+        Game::new(board, vec![piece], vec![constraint])
+    }
+
+    #[test]
+    fn test_pending_constraint_blocks_win() {
+        let game = game_with_uncoverable_constraint();
+        let placement = Placement::new(game.pieces[0], Point::new(0, 0), Direction::East);
+        let played = game.play(&placement).unwrap();
+        assert!(played.board.is_empty() && played.pieces.is_empty());
+        assert!(!played.is_won());
+    }
+
+    #[test]
+    fn test_solver_does_not_report_win_with_pending_constraint() {
+        let game = game_with_uncoverable_constraint();
+        assert!(solver::solve(game).is_err());
+    }
+}
@@ -35,24 +35,14 @@ fn run() -> Result<(), String> {
     if token == "all" {
         count_all(&puzzle, date)?;
     } else {
-        let difficulty = parse_difficulty(&token)?;
+        let difficulty = token
+            .parse::<Difficulty>()
+            .map_err(|err| format!("{} Use 'all' to run every difficulty.", err))?;
         count_single(&puzzle, date, difficulty)?;
     }
     Ok(())
 }
 
-fn parse_difficulty(token: &str) -> Result<Difficulty, String> {
-    match token {
-        "easy" => Ok(Difficulty::Easy),
-        "medium" => Ok(Difficulty::Medium),
-        "hard" => Ok(Difficulty::Hard),
-        other => Err(format!(
-            "Unknown difficulty '{}'. Expected easy, medium, hard, or all.",
-            other
-        )),
-    }
-}
-
 fn count_all(puzzle: &NytPuzzle, date: NaiveDate) -> Result<(), String> {
     for (idx, difficulty) in Difficulty::all().iter().copied().enumerate() {
         if idx > 0 {
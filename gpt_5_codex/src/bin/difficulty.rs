@@ -0,0 +1,73 @@
+use pips_solver::{loader, solver};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+fn main() {
+    if let Err(err) = run() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut args = env::args().skip(1);
+    let usage = "Usage: difficulty <puzzle-directory>";
+    let directory = PathBuf::from(args.next().ok_or_else(|| usage.to_string())?);
+    if args.next().is_some() {
+        return Err(usage.to_string());
+    }
+
+    println!("filename,cells,pieces,constraint_density,nodes,difficulty");
+    for path in puzzle_files(&directory)? {
+        match report_row(&path) {
+            Ok(row) => println!("{}", row),
+            Err(err) => eprintln!("Skipping {}: {}", path.display(), err),
+        }
+    }
+    Ok(())
+}
+
+/// Text puzzle files in `directory`, sorted by name so the report order is stable.
+fn puzzle_files(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|err| format!("Failed to read {}: {}", directory.display(), err))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("Failed to read {}: {}", directory.display(), err))?
+            .path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn report_row(path: &Path) -> Result<String, String> {
+    let game = loader::load_game_from_path(path)?;
+    let nodes = solver::estimate_difficulty(&game)?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(format!(
+        "{},{},{},{:.3},{},{}",
+        csv_field(&name),
+        game.board.len(),
+        game.pieces.len(),
+        game.constraint_density(),
+        nodes,
+        game.difficulty_label()?
+    ))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
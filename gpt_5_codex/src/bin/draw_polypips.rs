@@ -1,5 +1,5 @@
-use pips_solver::display;
-use pips_solver::model::{Board, Game, Piece, Pips, Placement, Point, PolyShape};
+use pips_solver::model::{Board, Constraint, Game, Piece, Pips, Placement, Point, PolyShape};
+use pips_solver::{display, loader};
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::process;
@@ -14,11 +14,14 @@ fn main() {
 fn run() -> Result<(), String> {
     let mut args = env::args().skip(1);
     let mut compact = false;
+    let mut constraint_mode = false;
     let mut tokens = Vec::new();
 
     while let Some(arg) = args.next() {
         if arg == "--compact" {
             compact = true;
+        } else if arg == "--constraint" {
+            constraint_mode = true;
         } else if arg.starts_with('-') {
             return Err(format!("Unknown flag '{}'.", arg));
         } else {
@@ -28,13 +31,18 @@ fn run() -> Result<(), String> {
 
     if tokens.is_empty() {
         return Err("Usage: draw-polypips [--compact] <piece-token> [...]\n\
-             Example: draw-polypips 5Z-:12345"
+             \x20      draw-polypips --constraint <constraint-token> [...]\n\
+             Example: draw-polypips 5Z-:12345\n\
+             Example: draw-polypips --constraint 'Exactly:7:(0,0),(1,0)'"
             .to_string());
     }
 
     for (index, token) in tokens.iter().enumerate() {
-        let parsed = parse_piece_token(token)?;
-        let lines = render_piece(&parsed, compact)?;
+        let lines = if constraint_mode {
+            render_constraint(&parse_constraint_token(token)?)
+        } else {
+            render_piece(&parse_piece_token(token)?, compact)?
+        };
         for line in lines {
             println!("{}", line);
         }
@@ -46,6 +54,30 @@ fn run() -> Result<(), String> {
     Ok(())
 }
 
+/// Parses `kind:argument:points`, e.g. `Exactly:7:(0,0),(1,0)` or `AllDifferent::(0,0),(0,1)`,
+/// into the loader's constraint syntax.
+fn parse_constraint_token(token: &str) -> Result<Constraint, String> {
+    let parts: Vec<&str> = token.trim().splitn(3, ':').collect();
+    let [kind, argument, points] = parts[..] else {
+        return Err(format!(
+            "Constraint token '{}' must be of the form kind:argument:points (e.g., Exactly:7:(0,0),(1,0)).",
+            token
+        ));
+    };
+    let argument = match (kind.trim(), argument.trim()) {
+        ("AllDifferent", "") => "{}",
+        (_, argument) => argument,
+    };
+    loader::parse_constraint(&format!("{} {} {{{}}}", kind.trim(), argument, points))
+}
+
+/// Draws `constraint` on a board made of exactly its points.
+fn render_constraint(constraint: &Constraint) -> Vec<String> {
+    let board = Board::new(constraint.points().clone());
+    let game = Game::new(board, Vec::new(), vec![constraint.clone()]);
+    display::render_unsolved(&game)
+}
+
 struct ParsedPiece {
     piece: Piece,
     pip_order: Vec<Pips>,
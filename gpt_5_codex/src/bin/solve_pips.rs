@@ -42,7 +42,10 @@ fn run() -> Result<(), String> {
         let puzzle = nyt::fetch_puzzle(date)?;
         solve_all(&puzzle, date, &run_opts)?;
     } else {
-        let difficulty = parse_difficulty(&args.difficulty)?;
+        let difficulty = args
+            .difficulty
+            .parse::<Difficulty>()
+            .map_err(|err| format!("{} Use 'all' to run every difficulty.", err))?;
         let puzzle = nyt::fetch_puzzle(date)?;
         solve_single(&puzzle, date, difficulty, &run_opts)?;
     }
@@ -86,18 +89,6 @@ struct RunOptions {
     show_playout: bool,
 }
 
-fn parse_difficulty(token: &str) -> Result<Difficulty, String> {
-    match token {
-        "easy" => Ok(Difficulty::Easy),
-        "medium" => Ok(Difficulty::Medium),
-        "hard" => Ok(Difficulty::Hard),
-        other => Err(format!(
-            "Unknown difficulty '{}'. Expected easy, medium, hard, or all.",
-            other
-        )),
-    }
-}
-
 fn solve_all(puzzle: &NytPuzzle, date: NaiveDate, options: &RunOptions) -> Result<(), String> {
     for (idx, difficulty) in Difficulty::all().iter().copied().enumerate() {
         if idx > 0 {
@@ -134,7 +125,8 @@ fn solve_and_print(
             for line in &unsolved {
                 println!("{}", line);
             }
-            let domino_lines = display::render_dominoes(&game.pieces);
+            let domino_lines =
+                display::render_domino_inventory(&game.pieces, display::terminal_width());
             if !domino_lines.is_empty() {
                 println!("\nDominoes:\n");
                 for line in domino_lines {
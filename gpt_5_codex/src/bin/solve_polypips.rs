@@ -6,6 +6,7 @@ use std::time::Instant;
 struct CliOptions {
     show_game: bool,
     show_playout: bool,
+    compact: bool,
     path: String,
     solver: SolverKind,
 }
@@ -32,7 +33,12 @@ fn run() -> Result<(), String> {
             for line in &unsolved {
                 println!("{}", line);
             }
-            let piece_lines = display::render_dominoes(&game.pieces);
+            let width = if options.compact {
+                usize::MAX
+            } else {
+                display::terminal_width()
+            };
+            let piece_lines = display::render_dominoes(&game.pieces, width);
             if !piece_lines.is_empty() {
                 println!("\nPieces:\n");
                 for line in piece_lines {
@@ -71,12 +77,14 @@ fn parse_args() -> Result<CliOptions, String> {
     let mut positional = Vec::new();
     let mut show_game = false;
     let mut show_playout = false;
+    let mut compact = false;
     let mut solver = SolverKind::V2;
 
     for arg in env::args().skip(1) {
         match arg.as_str() {
             "--show-game" => show_game = true,
             "--show-playout" => show_playout = true,
+            "--compact" => compact = true,
             other if other.starts_with("--") => {
                 if let Some(value) = other.strip_prefix("--solver=") {
                     solver = parse_solver_flag(value)?;
@@ -90,13 +98,15 @@ fn parse_args() -> Result<CliOptions, String> {
 
     if positional.len() != 1 {
         return Err(
-            "Usage: solve-polypips [--show-game] [--show-playout] <path-to-game-file>".to_string(),
+            "Usage: solve-polypips [--show-game [--compact]] [--show-playout] [--solver=v2|legacy] <path-to-game-file>"
+                .to_string(),
         );
     }
 
     Ok(CliOptions {
         show_game,
         show_playout,
+        compact,
         path: positional.remove(0),
         solver,
     })
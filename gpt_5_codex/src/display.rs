@@ -1,16 +1,103 @@
 use crate::model::{
-    Game, constraint::Constraint, piece::Piece, placement::Placement, point::Point,
+    Board, Game,
+    constraint::Constraint,
+    piece::{Piece, PolyShape},
+    placement::Placement,
+    point::Point,
 };
 use std::collections::{HashMap, HashSet};
 
 const CELL_WIDTH: usize = 3;
+const MARKER_GLYPH: &str = "●";
+/// Inventory width used when the terminal width is unknown.
+pub const DEFAULT_INVENTORY_WIDTH: usize = 80;
+
+/// Rendering choices for `render_unsolved_with`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DisplayOptions {
+    /// Merge touching unconstrained cells into one open area instead of boxing each cell.
+    pub blend_unconstrained: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            blend_unconstrained: true,
+        }
+    }
+}
 
 pub fn render_unsolved(game: &Game) -> Vec<String> {
-    let layout = BoardLayout::with_constraints(game);
+    render_unsolved_with(game, &DisplayOptions::default())
+}
+
+pub fn render_unsolved_with(game: &Game, options: &DisplayOptions) -> Vec<String> {
+    let mut layout = BoardLayout::with_constraints(game);
+    layout.blend_unconstrained = options.blend_unconstrained;
     layout.render(|cell| cell.label.clone())
 }
 
+/// Draws the bare board, one box per cell, with `MARKER_GLYPH` in the `marker` cell. Handy
+/// for seeing which cell a heuristic such as `Game::pivot_point` picked.
+pub fn render_with_marker(board: &Board, marker: Point) -> Vec<String> {
+    let game = Game::new(board.clone(), Vec::new(), Vec::new());
+    let layout = BoardLayout::with_dominoes(&game, &[]);
+    layout.render(|cell| {
+        if cell.point == marker {
+            MARKER_GLYPH.to_string()
+        } else {
+            String::new()
+        }
+    })
+}
+
+/// Draws the bare board with its x coordinates above each column and y coordinates down the
+/// left, so cells can be read off as `(x,y)` when writing constraints by hand.
+pub fn render_with_ruler(board: &Board) -> Vec<String> {
+    let Some((min_x, max_x, min_y, max_y)) = board.bounds() else {
+        return Vec::new();
+    };
+    let game = Game::new(board.clone(), Vec::new(), Vec::new());
+    let art = BoardLayout::with_dominoes(&game, &[]).render(|_| String::new());
+    let gutter = max_y.to_string().len() + 1;
+
+    let mut header = " ".repeat(gutter);
+    for x in min_x..=max_x {
+        header.push(' ');
+        header.push_str(&center_text(&x.to_string(), CELL_WIDTH));
+    }
+    let mut lines = vec![header.trim_end().to_string()];
+    for (index, line) in art.into_iter().enumerate() {
+        let label = if index % 2 == 1 {
+            (min_y + index as u32 / 2).to_string()
+        } else {
+            String::new()
+        };
+        lines.push(format!("{:>width$} {}", label, line, width = gutter - 1));
+    }
+    lines
+}
+
+/// The `(rows, cols)` character canvas `render_solution` draws `game` on, worked out from the
+/// board bounds so a UI can size its view without rendering first.
+pub fn render_dimensions(game: &Game) -> (usize, usize) {
+    match game.board.bounds() {
+        Some((min_x, max_x, min_y, max_y)) => {
+            let rows = (max_y - min_y + 1) as usize;
+            let cols = (max_x - min_x + 1) as usize;
+            (rows * 2 + 1, cols * (CELL_WIDTH + 1) + 1)
+        }
+        None => (0, 0),
+    }
+}
+
 pub fn render_solution(game: &Game, placements: &[Placement]) -> Vec<String> {
+    render_solution_colored(game, placements, false)
+}
+
+/// `render_solution`, with `color` drawing each placement's pips in the palette color
+/// `render_playout` gives its line, so board cells and playout lines cross-reference.
+pub fn render_solution_colored(game: &Game, placements: &[Placement], color: bool) -> Vec<String> {
     let layout = BoardLayout::with_dominoes(game, placements);
     let mut assignments = HashMap::new();
     for placement in placements {
@@ -18,15 +105,368 @@ pub fn render_solution(game: &Game, placements: &[Placement]) -> Vec<String> {
             assignments.insert(assignment.point, assignment.pips.value());
         }
     }
+    // `with_dominoes` numbers covered cells' regions by placement index.
+    let palette_code = |cell: &CellData| {
+        (color && cell.region < placements.len())
+            .then(|| PLACEMENT_PALETTE[cell.region % PLACEMENT_PALETTE.len()])
+    };
+    layout.frame().overlay_colored(
+        |cell| {
+            assignments
+                .get(&cell.point)
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        },
+        palette_code,
+    )
+}
+
+/// Solution view that keeps constraint regions visible: heavy lines outline constraint regions
+/// and the board edge, light lines separate placements inside them. Each board row takes two
+/// text rows, the constraint label (in the region's top-left cell) above the pip value.
+pub fn render_solution_with_constraint_borders(
+    game: &Game,
+    placements: &[Placement],
+) -> Vec<String> {
+    let Some((min_x, max_x, min_y, max_y)) = game.board.bounds() else {
+        return Vec::new();
+    };
+    let rows = (max_y - min_y + 1) as isize;
+    let cols = (max_x - min_x + 1) as isize;
+
+    let mut constraint_of = HashMap::new();
+    let mut labels = HashMap::new();
+    for (index, constraint) in game.constraints.iter().enumerate() {
+        for point in constraint.points() {
+            constraint_of.insert(*point, index);
+        }
+        if let Some(first) = constraint
+            .points()
+            .iter()
+            .min_by_key(|point| (point.y, point.x))
+        {
+            labels.insert(*first, label_for_constraint(constraint));
+        }
+    }
+    let mut placement_of = HashMap::new();
+    let mut pips = HashMap::new();
+    for (index, placement) in placements.iter().enumerate() {
+        for assignment in placement.assignments() {
+            placement_of.insert(assignment.point, index);
+            pips.insert(assignment.point, assignment.pips.value());
+        }
+    }
+
+    let cell_at = |row: isize, col: isize| {
+        if row < 0 || col < 0 || row >= rows || col >= cols {
+            return None;
+        }
+        let point = Point::new(min_x + col as u32, min_y + row as u32);
+        game.board.contains_point(&point).then_some(point)
+    };
+    // 0 = no line, 1 = light (placement edge), 2 = heavy (constraint or board edge).
+    let edge = |a: Option<Point>, b: Option<Point>| -> u8 {
+        match (a, b) {
+            (None, None) => 0,
+            (Some(a), Some(b)) if constraint_of.get(&a) != constraint_of.get(&b) => 2,
+            (Some(a), Some(b)) if placement_of.get(&a) != placement_of.get(&b) => 1,
+            (Some(_), Some(_)) => 0,
+            _ => 2,
+        }
+    };
+
+    let draw_rows = rows as usize * 3 + 1;
+    let draw_cols = cols as usize * (CELL_WIDTH + 1) + 1;
+    let mut grid = vec![vec![' '; draw_cols]; draw_rows];
+    for row in 0..=rows {
+        for col in 0..=cols {
+            let top = row as usize * 3;
+            let left = col as usize * (CELL_WIDTH + 1);
+            let north = edge(cell_at(row - 1, col - 1), cell_at(row - 1, col));
+            let south = edge(cell_at(row, col - 1), cell_at(row, col));
+            let west = edge(cell_at(row - 1, col - 1), cell_at(row, col - 1));
+            let east = edge(cell_at(row - 1, col), cell_at(row, col));
+            let junction = NodeEdges {
+                north: north > 0,
+                south: south > 0,
+                east: east > 0,
+                west: west > 0,
+            }
+            .to_char();
+            grid[top][left] = if north.max(south).max(east).max(west) == 2 {
+                heavy_line(junction)
+            } else {
+                junction
+            };
+            if col < cols && east > 0 {
+                let line = if east == 2 { '━' } else { '─' };
+                for offset in 1..=CELL_WIDTH {
+                    grid[top][left + offset] = line;
+                }
+            }
+            if row < rows && south > 0 {
+                let line = if south == 2 { '┃' } else { '│' };
+                grid[top + 1][left] = line;
+                grid[top + 2][left] = line;
+            }
+            if let (true, true, Some(point)) = (row < rows, col < cols, cell_at(row, col)) {
+                let label = labels.get(&point).cloned().unwrap_or_default();
+                let value = pips.get(&point).map(u8::to_string).unwrap_or_default();
+                for (line, text) in [(top + 1, label), (top + 2, value)] {
+                    let formatted = center_text(&sanitize_text(&text), CELL_WIDTH);
+                    for (i, ch) in formatted.chars().enumerate() {
+                        grid[line][left + 1 + i] = ch;
+                    }
+                }
+            }
+        }
+    }
+
+    grid.into_iter()
+        .map(|line| line.into_iter().collect::<String>().trim_end().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// The heavy counterpart of a light box-drawing character.
+fn heavy_line(light: char) -> char {
+    match light {
+        '│' => '┃',
+        '─' => '━',
+        '└' => '┗',
+        '┌' => '┏',
+        '├' => '┣',
+        '┘' => '┛',
+        '┐' => '┓',
+        '┤' => '┫',
+        '┴' => '┻',
+        '┬' => '┳',
+        '┼' => '╋',
+        other => other,
+    }
+}
+
+/// GitHub-flavored Markdown table of the solution: a header of x coordinates, then one row
+/// per board row led by its y coordinate. Off-board and uncovered cells are left empty.
+pub fn render_solution_markdown(game: &Game, placements: &[Placement]) -> String {
+    let Some((min_x, max_x, min_y, max_y)) = game.board.bounds() else {
+        return String::new();
+    };
+    let assignments: HashMap<Point, u8> = placements
+        .iter()
+        .flat_map(|placement| placement.assignments())
+        .map(|assignment| (assignment.point, assignment.pips.value()))
+        .collect();
+    let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = Vec::with_capacity((max_y - min_y + 3) as usize);
+    let header = std::iter::once(String::new()).chain((min_x..=max_x).map(|x| x.to_string()));
+    lines.push(row(header.collect()));
+    lines.push(row(vec!["---".to_string(); (max_x - min_x + 2) as usize]));
+    for y in min_y..=max_y {
+        let cells = (min_x..=max_x).map(|x| {
+            let point = Point::new(x, y);
+            match assignments.get(&point) {
+                Some(value) if game.board.contains_point(&point) => value.to_string(),
+                _ => String::new(),
+            }
+        });
+        lines.push(row(std::iter::once(y.to_string()).chain(cells).collect()));
+    }
+    lines.join("\n")
+}
+
+/// Like `render_solution`, but draws each pip value as a die face (see `Pips::as_die_face`).
+pub fn render_solution_faces(game: &Game, placements: &[Placement]) -> Vec<String> {
+    let layout = BoardLayout::with_dominoes(game, placements);
+    let mut assignments = HashMap::new();
+    for placement in placements {
+        for assignment in placement.assignments() {
+            assignments.insert(assignment.point, assignment.pips.as_die_face());
+        }
+    }
     layout.render(|cell| {
         assignments
             .get(&cell.point)
-            .map(|value| value.to_string())
+            .map(|face| face.to_string())
             .unwrap_or_default()
     })
 }
 
-pub fn render_dominoes(pieces: &[Piece]) -> Vec<String> {
+/// Like `render_solution`, but fills each cell with a letter identifying its placement
+/// (`A`, `B`, ... in placement order) so piece outlines read at a glance.
+pub fn render_shapes(game: &Game, placements: &[Placement]) -> Vec<String> {
+    let layout = BoardLayout::with_dominoes(game, placements);
+    layout.render(|cell| {
+        if cell.region < placements.len() {
+            placement_letter(cell.region).to_string()
+        } else {
+            String::new()
+        }
+    })
+}
+
+fn placement_letter(index: usize) -> char {
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    LETTERS.get(index).map(|&byte| byte as char).unwrap_or('?')
+}
+
+/// Explains each distinct label that `render_unsolved` draws for this game, one per line.
+pub fn render_legend(game: &Game) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut lines = Vec::new();
+    for constraint in &game.constraints {
+        let label = label_for_constraint(constraint);
+        if seen.insert(label.clone()) {
+            lines.push(format!(
+                "{:>3}  {}",
+                label,
+                meaning_for_constraint(constraint)
+            ));
+        }
+    }
+    let constrained: HashSet<Point> = game
+        .constraints
+        .iter()
+        .flat_map(|constraint| constraint.points().iter().copied())
+        .collect();
+    if game.board.iter().any(|point| !constrained.contains(&point)) {
+        lines.push(format!(
+            "{:>3}  {}",
+            "∅", "unconstrained region; any pips may go here"
+        ));
+    }
+    lines
+}
+
+const SVG_CELL: u32 = 40;
+
+/// SVG drawing of the unsolved board: each constraint region is tinted by its kind and
+/// labelled in its top-left cell, as in `render_unsolved`. Unconstrained cells stay white.
+pub fn render_unsolved_svg(game: &Game) -> String {
+    let Some((min_x, max_x, min_y, max_y)) = game.board.bounds() else {
+        return "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"0\" height=\"0\"></svg>\n"
+            .to_string();
+    };
+    let width = (max_x - min_x + 1) * SVG_CELL;
+    let height = (max_y - min_y + 1) * SVG_CELL;
+
+    let mut fills: HashMap<Point, &'static str> = HashMap::new();
+    let mut labels = Vec::new();
+    for constraint in &game.constraints {
+        let fill = svg_fill_for_constraint(constraint);
+        for point in constraint.points().iter() {
+            fills.insert(*point, fill);
+        }
+        if let Some(corner) = constraint
+            .points()
+            .iter()
+            .min_by_key(|point| (point.y, point.x))
+        {
+            labels.push((*corner, label_for_constraint(constraint)));
+        }
+    }
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n",
+        width, height, width, height
+    );
+    let mut points: Vec<Point> = game.board.iter().collect();
+    points.sort_by_key(|point| (point.y, point.x));
+    for point in points {
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#333333\"/>\n",
+            (point.x - min_x) * SVG_CELL,
+            (point.y - min_y) * SVG_CELL,
+            SVG_CELL,
+            SVG_CELL,
+            fills.get(&point).copied().unwrap_or("#ffffff")
+        ));
+    }
+    for (point, label) in labels {
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"14\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+            (point.x - min_x) * SVG_CELL + SVG_CELL / 2,
+            (point.y - min_y) * SVG_CELL + SVG_CELL / 2,
+            escape_xml(&label)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn svg_fill_for_constraint(constraint: &Constraint) -> &'static str {
+    match constraint {
+        Constraint::Exactly { .. } => "#9ecbff",
+        Constraint::AllSame { .. } => "#a8e6a1",
+        Constraint::AllDifferent { .. } => "#ffc58a",
+        Constraint::LessThan { .. } | Constraint::MoreThan { .. } => "#d2b4f0",
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// ANSI foreground codes cycled through placement numbers in colored output.
+const PLACEMENT_PALETTE: [u8; 12] = [31, 32, 33, 34, 35, 36, 91, 92, 93, 94, 95, 96];
+
+/// Whether stdout is a terminal and `$NO_COLOR` is unset.
+pub fn color_enabled() -> bool {
+    use std::io::IsTerminal;
+    std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+/// Numbered `n: placement` lines. With `color`, each line takes the palette color of its
+/// placement number, as do that placement's cells in `render_solution_colored`.
+pub fn render_playout(placements: &[Placement], color: bool) -> Vec<String> {
+    placements
+        .iter()
+        .enumerate()
+        .map(|(index, placement)| {
+            let line = format!("{}: {}", index + 1, placement);
+            if color {
+                let code = PLACEMENT_PALETTE[index % PLACEMENT_PALETTE.len()];
+                format!("\x1b[{}m{}\x1b[0m", code, line)
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Removes ANSI escape sequences (`ESC [ ... letter`) from `line`.
+pub fn strip_ansi(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            for next in chars.by_ref() {
+                if next.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(ch);
+        }
+    }
+    plain
+}
+
+/// Terminal width from `$COLUMNS`, or `DEFAULT_INVENTORY_WIDTH` if it is unset or invalid.
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_INVENTORY_WIDTH)
+}
+
+/// Sorted `code:pips` tokens, comma-separated and wrapped at `max_width` characters. Pass
+/// `usize::MAX` for a single line.
+pub fn render_dominoes(pieces: &[Piece], max_width: usize) -> Vec<String> {
     if pieces.is_empty() {
         return Vec::new();
     }
@@ -39,7 +479,6 @@ pub fn render_dominoes(pieces: &[Piece]) -> Vec<String> {
         .collect();
     tokens.sort();
 
-    const MAX_WIDTH: usize = 80;
     let mut lines = Vec::new();
     let mut current = String::new();
 
@@ -50,7 +489,7 @@ pub fn render_dominoes(pieces: &[Piece]) -> Vec<String> {
         }
 
         let projected_len = current.len() + 2 + token.len();
-        if projected_len <= MAX_WIDTH {
+        if projected_len <= max_width {
             current.push_str(", ");
             current.push_str(&token);
         } else {
@@ -66,6 +505,24 @@ pub fn render_dominoes(pieces: &[Piece]) -> Vec<String> {
     lines
 }
 
+/// Exemplar-style inventory for domino-only games: sorted `a-b` tokens, eight per line, or
+/// fewer if eight would not fit in `max_width` (usually `terminal_width()`). Falls back to
+/// `render_dominoes` when any piece is not a domino.
+pub fn render_domino_inventory(pieces: &[Piece], max_width: usize) -> Vec<String> {
+    if pieces
+        .iter()
+        .any(|piece| piece.shape() != PolyShape::Domino)
+    {
+        return render_dominoes(pieces, max_width);
+    }
+    // Single-digit `a-b` tokens sort lexicographically in numeric order.
+    let mut faces: Vec<String> = pieces.iter().map(Piece::display_face).collect();
+    faces.sort();
+    // Each face is 3 characters, separated by 3 spaces.
+    let per_line = (max_width.saturating_add(3) / 6).clamp(1, 8);
+    faces.chunks(per_line).map(|row| row.join("   ")).collect()
+}
+
 #[derive(Clone)]
 struct CellData {
     point: Point,
@@ -74,7 +531,8 @@ struct CellData {
     constrained: bool,
 }
 
-struct BoardLayout {
+/// Cell geometry and region borders for a board, as used by `render_solution`.
+pub struct BoardLayout {
     rows: usize,
     cols: usize,
     cells: Vec<Vec<Option<CellData>>>,
@@ -83,6 +541,12 @@ struct BoardLayout {
 }
 
 impl BoardLayout {
+    /// Draws `board`'s borders once, every cell boxed, so each frame of an animated solve
+    /// only writes its text via `PrecomputedLayout::render_values`.
+    pub fn precompute(board: &Board) -> PrecomputedLayout {
+        PrecomputedLayout::tiled(board, &[])
+    }
+
     fn with_constraints(game: &Game) -> Self {
         if game.board.is_empty() {
             return Self {
@@ -212,18 +676,27 @@ impl BoardLayout {
         }
     }
 
-    fn render<F>(&self, mut text_fn: F) -> Vec<String>
+    fn render<F>(&self, text_fn: F) -> Vec<String>
     where
         F: FnMut(&CellData) -> String,
     {
+        self.frame().overlay(text_fn)
+    }
+
+    /// Everything `render` draws except the cell text.
+    fn frame(&self) -> LayoutFrame {
         if self.rows == 0 || self.cols == 0 {
-            return Vec::new();
+            return LayoutFrame {
+                grid: Vec::new(),
+                slots: Vec::new(),
+            };
         }
 
         let draw_rows = self.rows * 2 + 1;
         let draw_cols = self.cols * (CELL_WIDTH + 1) + 1;
         let mut grid = vec![vec![' '; draw_cols]; draw_rows];
         let mut nodes = vec![vec![NodeEdges::default(); self.cols + 1]; self.rows + 1];
+        let mut slots = Vec::new();
 
         for row in 0..self.rows {
             for col in 0..self.cols {
@@ -269,11 +742,7 @@ impl BoardLayout {
                     nodes[row + 1][col + 1].north = true;
                 }
 
-                let text = sanitize_text(&text_fn(cell));
-                let formatted = center_text(&text, CELL_WIDTH);
-                for (i, ch) in formatted.chars().enumerate() {
-                    grid[base_row + 1][base_col + 1 + i] = ch;
-                }
+                slots.push((cell.clone(), base_row + 1, base_col + 1));
             }
         }
 
@@ -287,15 +756,7 @@ impl BoardLayout {
             }
         }
 
-        grid.into_iter()
-            .map(|mut line| {
-                while matches!(line.last(), Some(' ')) {
-                    line.pop();
-                }
-                line.into_iter().collect::<String>()
-            })
-            .filter(|line| !line.is_empty())
-            .collect()
+        LayoutFrame { grid, slots }
     }
 
     fn border_between(
@@ -332,6 +793,98 @@ impl BoardLayout {
     }
 }
 
+/// A board's rendered borders and text slots, computed once by `BoardLayout::precompute`.
+pub struct PrecomputedLayout {
+    board: Board,
+    frame: LayoutFrame,
+}
+
+impl PrecomputedLayout {
+    fn tiled(board: &Board, placements: &[Placement]) -> Self {
+        let game = Game::new(board.clone(), Vec::new(), Vec::new());
+        Self {
+            board: board.clone(),
+            frame: BoardLayout::with_dominoes(&game, placements).frame(),
+        }
+    }
+
+    /// The same board with borders around `placements` instead of around every cell. Build it
+    /// once per tiling; frames that only change text still go through `render_values`.
+    pub fn with_placements(&self, placements: &[Placement]) -> PrecomputedLayout {
+        Self::tiled(&self.board, placements)
+    }
+
+    /// Renders one frame by writing `values` into the cached grid; cells without a value are
+    /// left blank.
+    pub fn render_values(&self, values: &HashMap<Point, String>) -> Vec<String> {
+        self.frame
+            .overlay(|cell| values.get(&cell.point).cloned().unwrap_or_default())
+    }
+}
+
+/// A `BoardLayout` drawn without cell text.
+struct LayoutFrame {
+    grid: Vec<Vec<char>>,
+    /// Each cell with the grid row and column where its text starts.
+    slots: Vec<(CellData, usize, usize)>,
+}
+
+impl LayoutFrame {
+    fn overlay<F>(&self, text_fn: F) -> Vec<String>
+    where
+        F: FnMut(&CellData) -> String,
+    {
+        self.overlay_colored(text_fn, |_| None)
+    }
+
+    /// `overlay`, wrapping each cell's text in the ANSI foreground code `color_fn` picks for it.
+    fn overlay_colored<F, C>(&self, mut text_fn: F, mut color_fn: C) -> Vec<String>
+    where
+        F: FnMut(&CellData) -> String,
+        C: FnMut(&CellData) -> Option<u8>,
+    {
+        let mut grid = self.grid.clone();
+        let mut colors: Vec<HashMap<usize, u8>> = vec![HashMap::new(); grid.len()];
+        for (cell, row, col) in &self.slots {
+            let text = sanitize_text(&text_fn(cell));
+            let formatted = center_text(&text, CELL_WIDTH);
+            for (i, ch) in formatted.chars().enumerate() {
+                grid[*row][col + i] = ch;
+            }
+            if let Some(code) = color_fn(cell) {
+                colors[*row].insert(*col, code);
+            }
+        }
+
+        grid.into_iter()
+            .zip(colors)
+            .map(|(mut line, colors)| {
+                while matches!(line.last(), Some(' ')) {
+                    line.pop();
+                }
+                let mut text = String::new();
+                let mut reset_at = None;
+                for (index, ch) in line.iter().enumerate() {
+                    if reset_at == Some(index) {
+                        text.push_str("\x1b[0m");
+                        reset_at = None;
+                    }
+                    if let Some(code) = colors.get(&index) {
+                        text.push_str(&format!("\x1b[{}m", code));
+                        reset_at = Some(index + CELL_WIDTH);
+                    }
+                    text.push(*ch);
+                }
+                if reset_at.is_some() {
+                    text.push_str("\x1b[0m");
+                }
+                text
+            })
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+}
+
 #[derive(Copy, Clone, Default)]
 struct NodeEdges {
     north: bool,
@@ -403,6 +956,28 @@ fn label_for_constraint(constraint: &Constraint) -> String {
     }
 }
 
+fn meaning_for_constraint(constraint: &Constraint) -> String {
+    match constraint {
+        Constraint::AllSame { expected: None, .. } => {
+            "all pips in the region are the same value".to_string()
+        }
+        Constraint::AllSame {
+            expected: Some(pips),
+            ..
+        } => format!("all pips in the region are {}", pips.value()),
+        Constraint::AllDifferent { .. } => "all pips in the region are different".to_string(),
+        Constraint::Exactly { target, .. } => {
+            format!("pips in the region sum to exactly {}", target)
+        }
+        Constraint::LessThan { target, .. } => {
+            format!("pips in the region sum to less than {}", target)
+        }
+        Constraint::MoreThan { target, .. } => {
+            format!("pips in the region sum to more than {}", target)
+        }
+    }
+}
+
 fn label_unconstrained_regions(cells: &mut [Vec<Option<CellData>>]) {
     if cells.is_empty() || cells[0].is_empty() {
         return;
@@ -474,3 +1049,439 @@ fn label_unconstrained_regions(cells: &mut [Vec<Option<CellData>>]) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BoardLayout, CELL_WIDTH, DEFAULT_INVENTORY_WIDTH, DisplayOptions, render_dimensions,
+        render_domino_inventory, render_dominoes, render_legend, render_playout, render_shapes,
+        render_solution, render_solution_colored, render_solution_faces, render_solution_markdown,
+        render_solution_with_constraint_borders, render_unsolved_svg, render_unsolved_with,
+        render_with_marker, render_with_ruler, strip_ansi,
+    };
+    use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point, PolyShape};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+
+    fn domino(a: u8, b: u8) -> Piece {
+        Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap())
+    }
+
+    fn points(coords: &[(u32, u32)]) -> Arc<HashSet<Point>> {
+        Arc::new(coords.iter().map(|&(x, y)| Point::new(x, y)).collect())
+    }
+
+    #[test]
+    fn legend_explains_sum_and_all_different() {
+        let board = Board::new(
+            points(&[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (3, 1)])
+                .as_ref()
+                .clone(),
+        );
+        let constraints = vec![
+            Constraint::Exactly {
+                target: 7,
+                points: points(&[(0, 0), (1, 0)]),
+            },
+            Constraint::AllDifferent {
+                excluded: Arc::new(HashSet::new()),
+                points: points(&[(0, 1), (1, 1)]),
+            },
+        ];
+        let game = Game::new(
+            board,
+            vec![domino(3, 4), domino(1, 2), domino(0, 0)],
+            constraints,
+        );
+
+        let legend = render_legend(&game);
+        assert_eq!(legend.len(), 3);
+        assert!(
+            legend
+                .iter()
+                .any(|line| line.contains('7') && line.contains("exactly 7"))
+        );
+        assert!(
+            legend
+                .iter()
+                .any(|line| line.contains('≠') && line.contains("different"))
+        );
+        assert!(legend.iter().any(|line| line.contains('∅')));
+    }
+
+    #[test]
+    fn shapes_view_gives_each_placement_its_own_letter() {
+        let board = Board::new(
+            points(&[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1)])
+                .as_ref()
+                .clone(),
+        );
+        let ell = Piece::new(
+            PolyShape::TriL,
+            vec![
+                Pips::new(1).unwrap(),
+                Pips::new(2).unwrap(),
+                Pips::new(3).unwrap(),
+            ],
+        )
+        .unwrap();
+        let bar = domino(4, 5);
+        let placements = vec![
+            Placement::new(ell.clone(), Point::new(0, 0), 0, ell.pips().to_vec()),
+            Placement::new(bar.clone(), Point::new(1, 1), 0, bar.pips().to_vec()),
+        ];
+        let game = Game::new(board, vec![ell, bar], vec![]);
+
+        let rendered = render_shapes(&game, &placements).join("\n");
+        assert_eq!(rendered.matches('A').count(), 3);
+        assert_eq!(rendered.matches('B').count(), 2);
+        assert!(!rendered.chars().any(|ch| ch.is_ascii_digit()));
+    }
+
+    #[test]
+    fn unsolved_svg_tints_regions_by_constraint_kind() {
+        let board = Board::new(
+            points(&[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)])
+                .as_ref()
+                .clone(),
+        );
+        let constraints = vec![
+            Constraint::Exactly {
+                target: 5,
+                points: points(&[(0, 0), (1, 0)]),
+            },
+            Constraint::AllSame {
+                expected: None,
+                points: points(&[(2, 0), (2, 1)]),
+            },
+            Constraint::LessThan {
+                target: 3,
+                points: points(&[(0, 1)]),
+            },
+        ];
+        let game = Game::new(
+            board,
+            vec![domino(1, 4), domino(2, 2), domino(0, 1)],
+            constraints,
+        );
+
+        let svg = render_unsolved_svg(&game);
+        let fills: HashSet<&str> = svg
+            .lines()
+            .filter(|line| line.trim_start().starts_with("<rect"))
+            .filter_map(|line| line.split("fill=\"").nth(1))
+            .filter_map(|rest| rest.split('"').next())
+            .collect();
+        // Three constraint kinds plus white for the unconstrained cell.
+        assert_eq!(fills.len(), 4);
+        assert!(fills.contains("#ffffff"));
+        assert!(svg.contains("&lt;3"));
+        assert_eq!(svg.matches("<text").count(), 3);
+    }
+
+    #[test]
+    fn faces_view_draws_die_glyphs() {
+        let board = Board::new(points(&[(0, 0), (1, 0)]).as_ref().clone());
+        let piece = domino(0, 5);
+        let placements = vec![Placement::new(
+            piece.clone(),
+            Point::new(0, 0),
+            0,
+            piece.pips().to_vec(),
+        )];
+        let game = Game::new(board, vec![piece], vec![]);
+        let rendered = render_solution_faces(&game, &placements).join("\n");
+        assert!(rendered.contains('□'));
+        assert!(rendered.contains('⚄'));
+        assert!(!rendered.contains('5'));
+    }
+
+    #[test]
+    fn colored_playout_strips_back_to_plain() {
+        let placements: Vec<Placement> = (0..14)
+            .map(|x| {
+                let piece = domino(x % 7, 6 - x % 7);
+                Placement::new(
+                    piece.clone(),
+                    Point::new(x as u32 * 2, 0),
+                    0,
+                    piece.pips().to_vec(),
+                )
+            })
+            .collect();
+        let plain = render_playout(&placements, false);
+        let colored = render_playout(&placements, true);
+        assert_eq!(plain[0], format!("1: {}", placements[0]));
+        assert_ne!(colored, plain);
+        assert!(colored.iter().all(|line| line.starts_with("\x1b[")));
+        assert_eq!(colored[0][..5], colored[12][..5]);
+        let decolored: Vec<String> = colored.iter().map(|line| strip_ansi(line)).collect();
+        assert_eq!(decolored, plain);
+    }
+
+    #[test]
+    fn colored_solution_matches_playout_colors() {
+        let board = Board::new(points(&[(0, 0), (1, 0), (2, 0), (3, 0)]).as_ref().clone());
+        let pieces = [domino(1, 2), domino(3, 4)];
+        let placements: Vec<Placement> = pieces
+            .iter()
+            .enumerate()
+            .map(|(index, piece)| {
+                Placement::new(
+                    piece.clone(),
+                    Point::new(index as u32 * 2, 0),
+                    0,
+                    piece.pips().to_vec(),
+                )
+            })
+            .collect();
+        let game = Game::new(board, pieces.to_vec(), vec![]);
+
+        let plain = render_solution(&game, &placements);
+        let colored = render_solution_colored(&game, &placements, true);
+        let decolored: Vec<String> = colored.iter().map(|line| strip_ansi(line)).collect();
+        assert_eq!(decolored, plain);
+        assert_eq!(render_solution_colored(&game, &placements, false), plain);
+
+        let playout = render_playout(&placements, true);
+        for (index, line) in playout.iter().enumerate() {
+            let code = &line[..line.find('m').unwrap() + 1];
+            let pips = placements[index].piece.pips();
+            for pip in pips {
+                let cell = format!("{} {} \x1b[0m", code, pip.value());
+                assert!(colored[1].contains(&cell), "{:?}", colored[1]);
+            }
+        }
+    }
+
+    #[test]
+    fn domino_inventory_uses_exemplar_tokens() {
+        let pieces: Vec<Piece> = [
+            (1, 0),
+            (0, 0),
+            (5, 1),
+            (2, 1),
+            (3, 3),
+            (6, 0),
+            (4, 2),
+            (2, 2),
+            (6, 6),
+        ]
+        .iter()
+        .map(|&(a, b)| domino(a, b))
+        .collect();
+        let lines = render_domino_inventory(&pieces, DEFAULT_INVENTORY_WIDTH);
+        assert_eq!(
+            lines,
+            vec![
+                "0-0   0-1   0-6   1-2   1-5   2-2   2-4   3-3".to_string(),
+                "6-6".to_string(),
+            ]
+        );
+        let narrow = render_domino_inventory(&pieces, 21);
+        assert_eq!(narrow[0], "0-0   0-1   0-6   1-2");
+        assert_eq!(narrow.len(), 3);
+        assert!(narrow.iter().all(|line| line.len() <= 21));
+    }
+
+    #[test]
+    fn inventory_falls_back_to_shape_codes_for_polyominoes() {
+        let values: Vec<Pips> = (1..=5).map(|v| Pips::new(v).unwrap()).collect();
+        let pentomino = Piece::new(PolyShape::PentI, values).unwrap();
+        let pieces = [pentomino, domino(0, 5)];
+        let lines = render_domino_inventory(&pieces, DEFAULT_INVENTORY_WIDTH);
+        assert_eq!(lines, vec!["2I:05, 5I:12345".to_string()]);
+        let narrow = render_domino_inventory(&pieces, 10);
+        assert_eq!(narrow, vec!["2I:05".to_string(), "5I:12345".to_string()]);
+    }
+
+    #[test]
+    fn marker_appears_only_in_the_marked_cell() {
+        let board = Board::new(points(&[(0, 0), (1, 0), (2, 0), (1, 1)]).as_ref().clone());
+        let rendered = render_with_marker(&board, Point::new(2, 0));
+        let total: usize = rendered.iter().map(|line| line.matches('●').count()).sum();
+        assert_eq!(total, 1);
+        // Row 1 of the drawing holds the cell text for board row 0; each cell is
+        // CELL_WIDTH + 1 characters wide including its left border.
+        let row: Vec<char> = rendered[1].chars().collect();
+        let column = row.iter().position(|&ch| ch == '●').unwrap();
+        assert_eq!(column / (CELL_WIDTH + 1), 2);
+    }
+
+    #[test]
+    fn dominoes_wrap_at_the_requested_width() {
+        let pieces: Vec<Piece> = (0..6).map(|value| domino(value, value + 1)).collect();
+        let narrow = render_dominoes(&pieces, 20);
+        assert!(narrow.len() > 1);
+        assert!(narrow.iter().all(|line| line.len() <= 20));
+
+        let wide = render_dominoes(&pieces, usize::MAX);
+        assert_eq!(wide.len(), 1);
+        assert_eq!(wide[0], narrow.join(", "));
+    }
+
+    #[test]
+    fn unblended_view_boxes_each_free_cell() {
+        let board = Board::new(
+            points(&[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)])
+                .as_ref()
+                .clone(),
+        );
+        let constraints = vec![Constraint::Exactly {
+            target: 4,
+            points: points(&[(0, 0), (0, 1)]),
+        }];
+        let game = Game::new(
+            board,
+            vec![domino(1, 3), domino(2, 2), domino(0, 4)],
+            constraints,
+        );
+
+        let interior_borders = |lines: Vec<String>| -> usize {
+            lines[1..lines.len() - 1]
+                .iter()
+                .map(|line| {
+                    let chars: Vec<char> = line.chars().collect();
+                    chars[1..chars.len() - 1]
+                        .iter()
+                        .filter(|&&ch| "│─┼├┤┬┴".contains(ch))
+                        .count()
+                })
+                .sum()
+        };
+        let blended = render_unsolved_with(&game, &DisplayOptions::default());
+        let boxed = render_unsolved_with(
+            &game,
+            &DisplayOptions {
+                blend_unconstrained: false,
+            },
+        );
+        assert!(interior_borders(blended) < interior_borders(boxed));
+    }
+
+    #[test]
+    fn render_dimensions_match_rendered_solution() {
+        for fixture in ["poly_games/2x5.txt", "poly_games/3x3.txt"] {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(fixture);
+            let game = crate::loader::load_game_from_path(path).expect("load fixture");
+            let solution = crate::solver_v2::solve(&game).expect("fixture solves");
+            let lines = render_solution(&game, &solution);
+            let widest = lines.iter().map(|line| line.chars().count()).max();
+            assert_eq!(
+                render_dimensions(&game),
+                (lines.len(), widest.unwrap_or(0)),
+                "{}",
+                fixture
+            );
+        }
+    }
+
+    #[test]
+    fn constraint_borders_show_labels_pips_and_both_line_weights() {
+        let board = Board::new(points(&[(0, 0), (1, 0), (2, 0), (3, 0)]).as_ref().clone());
+        let (left, right) = (domino(1, 2), domino(5, 3));
+        let placements = vec![
+            Placement::new(left.clone(), Point::new(0, 0), 0, left.pips().to_vec()),
+            Placement::new(right.clone(), Point::new(2, 0), 0, right.pips().to_vec()),
+        ];
+        let constraints = vec![
+            Constraint::LessThan {
+                target: 9,
+                points: points(&[(0, 0), (1, 0), (2, 0)]),
+            },
+            Constraint::AllDifferent {
+                excluded: Arc::new(HashSet::new()),
+                points: points(&[(3, 0)]),
+            },
+        ];
+        let game = Game::new(board, vec![left, right], constraints);
+        let lines = render_solution_with_constraint_borders(&game, &placements);
+        let text = lines.join("\n");
+        assert!(text.contains("<9") && text.contains('≠'), "{}", text);
+        let pip_row: String = lines[2].chars().filter(char::is_ascii_digit).collect();
+        assert_eq!(pip_row, "1253");
+        // A light line splits the two dominoes inside the `<9` region; a heavy one ends it.
+        assert!(lines[2].contains('│') && lines[2].contains('┃'), "{}", text);
+        assert_eq!(lines.len(), 4);
+    }
+
+    #[test]
+    fn markdown_table_has_a_column_per_x_and_a_row_per_y() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../poly_games/2x5.txt");
+        let game = crate::loader::load_game_from_path(path).expect("load fixture");
+        let solution = crate::solver_v2::solve(&game).expect("fixture solves");
+        let (min_x, max_x, min_y, max_y) = game.board.bounds().unwrap();
+        let table = render_solution_markdown(&game, &solution);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), (max_y - min_y + 1) as usize + 2);
+        for line in &lines {
+            assert!(line.starts_with('|') && line.ends_with('|'), "{}", line);
+            assert_eq!(line.matches('|').count(), (max_x - min_x + 1) as usize + 2);
+        }
+        let filled = lines[2..]
+            .iter()
+            .flat_map(|line| line.split('|').skip(2))
+            .filter(|cell| !cell.trim().is_empty())
+            .count();
+        assert_eq!(filled, game.board.len());
+    }
+
+    #[test]
+    fn ruler_labels_two_digit_columns_and_rows() {
+        let board = Board::new(
+            (3..13)
+                .flat_map(|x| (0..11).map(move |y| Point::new(x, y)))
+                .collect(),
+        );
+        let lines = render_with_ruler(&board);
+        let header = &lines[0];
+        let labels: Vec<&str> = header.split_whitespace().collect();
+        assert_eq!(
+            labels,
+            ["3", "4", "5", "6", "7", "8", "9", "10", "11", "12"]
+        );
+
+        // Each label sits over its cell's interior, CELL_WIDTH + 1 characters apart.
+        let gutter = 3;
+        for (column, label) in labels.iter().enumerate() {
+            let start = gutter + column * (CELL_WIDTH + 1) + 1;
+            let slot = header
+                .get(start..start + CELL_WIDTH)
+                .unwrap_or(&header[start..]);
+            assert_eq!(slot.trim(), *label);
+        }
+
+        assert!(lines[2].starts_with(" 0 │"), "{}", lines[2]);
+        assert!(lines[22].starts_with("10 │"), "{}", lines[22]);
+        assert!(lines[1].starts_with("   ┌"), "{}", lines[1]);
+    }
+
+    #[test]
+    fn precomputed_layout_matches_render_solution() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("poly_games/3x3.txt");
+        let game = crate::loader::load_game_from_path(path).expect("load fixture");
+        let solution = crate::solver_v2::solve(&game).expect("fixture solves");
+        let layout = BoardLayout::precompute(&game.board);
+        assert_eq!(
+            layout.render_values(&HashMap::new()),
+            render_solution(&game, &[])
+        );
+
+        // One tiled layout serves every frame that fills in the solution's pips one by one.
+        let tiled = layout.with_placements(&solution);
+        let mut values: HashMap<Point, String> = HashMap::new();
+        for placement in &solution {
+            for assignment in placement.assignments() {
+                values.insert(assignment.point, assignment.pips.value().to_string());
+            }
+        }
+        assert_eq!(
+            tiled.render_values(&values),
+            render_solution(&game, &solution)
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::{Board, Piece, Pips, Placement, Point, PolyShape};
+use crate::util::math::gcd;
 use crate::util::rng::SimpleRng;
 use std::collections::HashSet;
 
@@ -47,11 +48,11 @@ pub fn generate(config: GeneratorConfig) -> Result<GeneratedPuzzle, String> {
 
     // Ensure shapes can tile the area in principle.
     let area = config.width * config.height;
-    let gcd = allowed
+    let size_gcd = allowed
         .iter()
         .map(|shape| shape.cell_count())
-        .fold(0usize, gcd_usize);
-    if gcd == 0 || area % gcd != 0 {
+        .fold(0usize, gcd);
+    if size_gcd == 0 || !area.is_multiple_of(size_gcd) {
         return Err("Board area is incompatible with available shapes.".to_string());
     }
 
@@ -228,20 +229,3 @@ fn build_piece(shape: PolyShape, pip_order: &[Pips]) -> Result<Piece, String> {
         Piece::new(shape, pip_order.to_vec())
     }
 }
-
-fn gcd_usize(a: usize, b: usize) -> usize {
-    if a == 0 {
-        return b;
-    }
-    if b == 0 {
-        return a;
-    }
-    let mut x = a;
-    let mut y = b;
-    while y != 0 {
-        let temp = y;
-        y = x % y;
-        x = temp;
-    }
-    x
-}
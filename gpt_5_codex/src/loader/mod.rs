@@ -8,25 +8,90 @@ use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
+/// Extra handling for `load_game_from_path_with`.
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// Drop constraints that every assignment satisfies (see `Constraint::is_trivial`),
+    /// warning on stderr for each, instead of rejecting or keeping them.
+    pub drop_trivial_constraints: bool,
+}
+
+/// What `load_game_from_path_with` produced: the game plus anything `LoadOptions` removed, so
+/// callers decide whether and how to report it.
+#[derive(Clone, Debug)]
+pub struct LoadReport {
+    pub game: Game,
+    /// Always-satisfied constraints dropped by `drop_trivial_constraints`, in file order.
+    pub dropped_constraints: Vec<Constraint>,
+}
+
 pub fn load_game_from_path<P: AsRef<Path>>(path: P) -> Result<Game, String> {
+    load_game_from_path_with(path, &LoadOptions::default()).map(|report| report.game)
+}
+
+pub fn load_game_from_path_with<P: AsRef<Path>>(
+    path: P,
+    options: &LoadOptions,
+) -> Result<LoadReport, String> {
     let file = File::open(path).map_err(|err| err.to_string())?;
-    load_game_from_reader(BufReader::new(file))
+    load_game_from_reader_with(BufReader::new(file), options)
 }
 
 pub fn load_game_from_reader<R: BufRead>(reader: R) -> Result<Game, String> {
+    load_game_from_reader_with(reader, &LoadOptions::default()).map(|report| report.game)
+}
+
+pub fn load_game_from_reader_with<R: BufRead>(
+    reader: R,
+    options: &LoadOptions,
+) -> Result<LoadReport, String> {
     let lines: Result<Vec<String>, _> = reader.lines().collect();
     let joined = lines.map_err(|err| err.to_string())?.join("\n");
-    parse_game(&joined)
+    parse_game_with(&joined, options)
 }
 
+#[cfg(test)]
 fn parse_game(contents: &str) -> Result<Game, String> {
+    parse_game_with(contents, &LoadOptions::default()).map(|report| report.game)
+}
+
+fn parse_game_with(contents: &str, options: &LoadOptions) -> Result<LoadReport, String> {
     let sections = ParsedSections::new(contents)?;
     let board = parse_board(&sections.board_lines)?;
     let pieces = parse_pieces(&sections.pieces_line)?;
-    let constraints = parse_constraints(&sections.constraint_lines)?;
+    let mut constraints = parse_constraints(&sections.constraint_lines)?;
+    let mut dropped_constraints = Vec::new();
+    if options.drop_trivial_constraints {
+        (dropped_constraints, constraints) = constraints
+            .into_iter()
+            .partition(|constraint| constraint.is_trivial());
+    }
     let game = Game::new(board, pieces, constraints);
-    game.validate()?;
-    Ok(game)
+    if let Err(err) = game.validate() {
+        return Err(off_board_error(&game).unwrap_or(err));
+    }
+    Ok(LoadReport {
+        game,
+        dropped_constraints,
+    })
+}
+
+/// Names the first constraint (in file order) that references a point missing from the board,
+/// along with that point, so hand-authored or converted files are quick to fix.
+fn off_board_error(game: &Game) -> Option<String> {
+    game.constraints.iter().find_map(|constraint| {
+        constraint
+            .points()
+            .iter()
+            .filter(|point| !game.board.contains_point(point))
+            .min_by_key(|point| (point.y, point.x))
+            .map(|point| {
+                format!(
+                    "Constraint {} references point {} that is not on the board.",
+                    constraint, point
+                )
+            })
+    })
 }
 
 struct ParsedSections {
@@ -70,11 +135,16 @@ impl ParsedSections {
         }
         let pieces_line = filtered_pieces.into_iter().next().unwrap_or_default();
 
-        expect_header(&mut lines, "constraints:")?;
-        let constraint_lines = collect_until_blank(&mut lines)
-            .into_iter()
-            .filter(|line| !line.trim().is_empty())
-            .collect();
+        // The constraints section is optional; a file may end right after its pieces.
+        let constraint_lines = if lines.peek().is_some() {
+            expect_header(&mut lines, "constraints:")?;
+            collect_until_section(&mut lines)
+                .into_iter()
+                .filter(|line| !line.trim().is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
 
         Ok(Self {
             board_lines,
@@ -101,22 +171,27 @@ where
     Err(format!("Missing {} header.", expected))
 }
 
-fn collect_until_blank<'a, I>(lines: &mut std::iter::Peekable<I>) -> Vec<String>
+/// Collects lines up to EOF or the next trailing section header (such as `game:` or
+/// `pieces (ascii):`), so blank lines between constraint groups do not end the section.
+fn collect_until_section<'a, I>(lines: &mut std::iter::Peekable<I>) -> Vec<String>
 where
     I: Iterator<Item = &'a str>,
 {
     let mut collected = Vec::new();
     while let Some(line) = lines.peek() {
-        if line.trim().is_empty() {
-            lines.next();
+        if is_section_header(line) {
             break;
-        } else {
-            collected.push(lines.next().unwrap().to_string());
         }
+        collected.push(lines.next().unwrap().to_string());
     }
     collected
 }
 
+fn is_section_header(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.ends_with(':') && !trimmed.contains('{')
+}
+
 fn collect_until_header<'a, I>(lines: &mut std::iter::Peekable<I>, header: &str) -> Vec<String>
 where
     I: Iterator<Item = &'a str>,
@@ -164,12 +239,14 @@ fn parse_pieces(line: &str) -> Result<Vec<Piece>, String> {
     Ok(pieces)
 }
 
+/// Parses one piece: a legacy two-digit domino such as `06`, or `SHAPE:pips` such as
+/// `5Z-:12345` for any `PolyShape` code, with one pip digit per cell.
 pub fn parse_piece_token(token: &str) -> Result<Piece, String> {
     let token = token.trim();
     if token.chars().all(|c| c.is_ascii_digit()) {
         if token.len() != 2 {
             return Err(format!(
-                "Invalid domino token '{}'. Expected two digits.",
+                "Invalid domino token '{}'. Expected two digits, or SHAPE:pips for other shapes.",
                 token
             ));
         }
@@ -225,7 +302,7 @@ fn parse_constraints(lines: &[String]) -> Result<ConstraintSet, String> {
     Ok(constraints)
 }
 
-fn parse_constraint(line: &str) -> Result<Constraint, String> {
+pub fn parse_constraint(line: &str) -> Result<Constraint, String> {
     let trimmed = line.trim();
     let brace_index = trimmed
         .rfind('{')
@@ -241,7 +318,7 @@ fn parse_constraint(line: &str) -> Result<Constraint, String> {
     let kind = tokens
         .next()
         .ok_or_else(|| "Missing constraint type.".to_string())?;
-    match kind {
+    let constraint = match kind {
         "AllSame" => {
             let arg = tokens
                 .next()
@@ -250,29 +327,37 @@ fn parse_constraint(line: &str) -> Result<Constraint, String> {
                 "None" => None,
                 value => Some(parse_pips_option(value)?),
             };
-            Ok(Constraint::AllSame { expected, points })
+            Constraint::AllSame { expected, points }
         }
         "AllDifferent" => {
             let arg = tokens
                 .next()
                 .ok_or_else(|| "Missing AllDifferent exclusions.".to_string())?;
             let excluded = Arc::new(parse_pip_set(arg)?);
-            Ok(Constraint::AllDifferent { excluded, points })
+            Constraint::AllDifferent { excluded, points }
         }
         "Exactly" => {
             let target = parse_u32(tokens.next(), "Exactly target")?;
-            Ok(Constraint::Exactly { target, points })
+            Constraint::Exactly { target, points }
         }
         "LessThan" => {
             let target = parse_u32(tokens.next(), "LessThan target")?;
-            Ok(Constraint::LessThan { target, points })
+            Constraint::LessThan { target, points }
         }
         "MoreThan" => {
             let target = parse_u32(tokens.next(), "MoreThan target")?;
-            Ok(Constraint::MoreThan { target, points })
+            Constraint::MoreThan { target, points }
         }
-        _ => Err(format!("Unknown constraint type '{}'.", kind)),
+        _ => return Err(format!("Unknown constraint type '{}'.", kind)),
+    };
+    if let Some(extra) = tokens.next() {
+        return Err(format!(
+            "Unexpected '{}' in constraint '{}'.",
+            extra,
+            line.trim()
+        ));
     }
+    Ok(constraint)
 }
 
 fn parse_points(spec: &str) -> Result<HashSet<Point>, String> {
@@ -290,25 +375,7 @@ fn parse_points(spec: &str) -> Result<HashSet<Point>, String> {
         if trimmed.is_empty() {
             continue;
         }
-        let mut parts = trimmed.split(',');
-        let x = parts
-            .next()
-            .ok_or_else(|| "Point is missing an x coordinate.".to_string())?;
-        let y = parts
-            .next()
-            .ok_or_else(|| "Point is missing a y coordinate.".to_string())?;
-        if parts.next().is_some() {
-            return Err("Point has too many coordinates.".to_string());
-        }
-        let x_val = x
-            .trim()
-            .parse::<u32>()
-            .map_err(|_| format!("Invalid x coordinate '{}'.", x))?;
-        let y_val = y
-            .trim()
-            .parse::<u32>()
-            .map_err(|_| format!("Invalid y coordinate '{}'.", y))?;
-        points.insert(Point::new(x_val, y_val));
+        points.insert(Point::from_coordinates(trimmed)?);
     }
     Ok(points)
 }
@@ -355,7 +422,8 @@ fn parse_digit(ch: char) -> Result<u8, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_game;
+    use super::{LoadOptions, parse_game, parse_game_with, parse_pieces};
+    use crate::model::{Piece, Pips, PolyShape};
 
     #[test]
     fn parses_example_game() {
@@ -390,4 +458,101 @@ Exactly 3 {(3,4)}
         assert_eq!(game.pieces.len(), 9);
         assert_eq!(game.constraints.len(), 10);
     }
+
+    #[test]
+    fn off_board_constraint_error_names_point_and_constraint() {
+        let input = r#"
+board:
+##
+##
+
+pieces:
+12,34
+
+constraints:
+Exactly 3 {(0,0),(1,0)}
+Exactly 9 {(1,1),(2,1)}
+"#;
+
+        let err = parse_game(input).expect_err("constraint leaves the board");
+        assert!(err.contains("(2, 1)"), "{}", err);
+        assert!(err.contains("Exactly(9"), "{}", err);
+    }
+
+    #[test]
+    fn constraints_section_is_optional() {
+        let input = "board:\n##\n##\n\npieces:\n12,34\n";
+        let game = parse_game(input).expect("game should parse");
+        assert_eq!(game.board.len(), 4);
+        assert_eq!(game.pieces.len(), 2);
+        assert!(game.constraints.is_empty());
+        assert!(game.validate().is_ok());
+    }
+
+    #[test]
+    fn constraints_may_be_separated_by_blank_lines() {
+        let input = "board:\n##\n##\n\npieces:\n12,34\n\nconstraints:\nExactly 3 {(0,0),(1,0)}\n\nLessThan 8 {(0,1),(1,1)}\n\ngame:\n┌───┐\n";
+        let game = parse_game(input).expect("game should parse");
+        assert_eq!(game.constraints.len(), 2);
+    }
+
+    #[test]
+    fn drops_trivial_constraints_only_when_asked() {
+        let input = "board:\n##\n\npieces:\n12\n\nconstraints:\nLessThan 20 {(0,0),(1,0)}\nExactly 1 {(0,0)}\n";
+        assert!(parse_game(input).is_err());
+
+        let options = LoadOptions {
+            drop_trivial_constraints: true,
+        };
+        let report = parse_game_with(input, &options).expect("trivial constraint dropped");
+        assert_eq!(report.game.constraints.len(), 1);
+        assert!(matches!(
+            report.game.constraints[0],
+            crate::model::Constraint::Exactly { target: 1, .. }
+        ));
+        assert_eq!(report.dropped_constraints.len(), 1);
+        assert!(matches!(
+            report.dropped_constraints[0],
+            crate::model::Constraint::LessThan { target: 20, .. }
+        ));
+    }
+
+    #[test]
+    fn multibyte_constraint_lines_error_instead_of_panicking() {
+        for line in [
+            "Exactly 3 {(é,0),(1,0)}",
+            "Exactly 3 {(0,0)é,(1,0)}",
+            "Exactly 3 {(0,0),(1,0)}€",
+            "Exactly 3 é{(0,0),(1,0)}",
+            "Exactlÿ 3 {(0,0),(1,0)}",
+            "AllSame Some(😀) {(0,0)}",
+        ] {
+            let input = format!("board:\n##\n\npieces:\n12\n\nconstraints:\n{}\n", line);
+            assert!(parse_game(&input).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn parses_pentomino_piece_tokens() {
+        let pieces = parse_pieces("5Z-:12345").unwrap();
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].shape(), PolyShape::PentZMinus);
+        let values: Vec<u8> = pieces[0].pips().iter().map(|pip| pip.value()).collect();
+        assert_eq!(values, vec![1, 2, 3, 4, 5]);
+
+        let err = parse_pieces("5Z-:1234").unwrap_err();
+        assert!(err.contains("requires 5 digits"), "{}", err);
+    }
+
+    #[test]
+    fn parses_mixed_domino_and_pentomino_line() {
+        let pieces = parse_pieces("06, 5P+:00112, 2I:34").unwrap();
+        let domino = |a, b| Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap());
+        assert_eq!(pieces[0], domino(0, 6));
+        assert_eq!(pieces[1].shape(), PolyShape::PentPPlus);
+        assert_eq!(pieces[2], domino(3, 4));
+
+        let err = parse_pieces("06,123").unwrap_err();
+        assert!(err.contains("SHAPE:pips"), "{}", err);
+    }
 }
@@ -1,12 +1,14 @@
 use super::load_game_from_reader;
-use crate::model::Game;
+use crate::model::{Constraint, Game, Point, PolyShape};
 use chrono::NaiveDate;
-use serde::Deserialize;
-use std::collections::BTreeSet;
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env;
-use std::fmt::Write as _;
-use std::io::Cursor;
+use std::fmt::{self, Write as _};
+use std::io::{Cursor, Read};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use ureq::Error as UreqError;
 
 const DEFAULT_BASE_URL: &str = "https://www.nytimes.com/svc/pips/v1";
@@ -40,6 +42,51 @@ impl Difficulty {
     }
 }
 
+impl FromStr for Difficulty {
+    type Err = String;
+
+    /// Accepts `easy`/`medium`/`hard` or their first letter, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "easy" | "e" => Ok(Difficulty::Easy),
+            "medium" | "m" => Ok(Difficulty::Medium),
+            "hard" | "h" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "Unknown difficulty '{}'. Expected easy, medium, or hard.",
+                s.trim()
+            )),
+        }
+    }
+}
+
+/// Failures from fetching an NYT puzzle, separating "not out yet" from real errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NytError {
+    NotPublished(NaiveDate),
+    Other(String),
+}
+
+impl fmt::Display for NytError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NytError::NotPublished(date) => write!(f, "No puzzle available for {} yet.", date),
+            NytError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<String> for NytError {
+    fn from(message: String) -> Self {
+        NytError::Other(message)
+    }
+}
+
+impl From<NytError> for String {
+    fn from(err: NytError) -> Self {
+        err.to_string()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct PuzzleFile {
     easy: GameDef,
@@ -47,18 +94,20 @@ struct PuzzleFile {
     hard: GameDef,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct GameDef {
+    #[serde(skip_serializing_if = "Option::is_none")]
     constructors: Option<String>,
     dominoes: Vec<[u8; 2]>,
     regions: Vec<Region>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct Region {
     indices: Vec<[u32; 2]>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     target: Option<u32>,
     #[serde(rename = "type")]
     kind: String,
@@ -76,21 +125,31 @@ impl NytPuzzle {
     }
 
     pub fn game(&self, difficulty: Difficulty) -> Result<Game, String> {
-        let (def, label) = match difficulty {
+        let (def, label) = self.def(difficulty);
+        convert_game(def, label)
+    }
+
+    /// Like `game`, but also returns one log line per region describing how it was mapped.
+    pub fn game_verbose(&self, difficulty: Difficulty) -> Result<(Game, Vec<String>), String> {
+        let (def, label) = self.def(difficulty);
+        convert_game_verbose(def, label)
+    }
+
+    fn def(&self, difficulty: Difficulty) -> (&GameDef, &'static str) {
+        match difficulty {
             Difficulty::Easy => (&self.inner.easy, "easy"),
             Difficulty::Medium => (&self.inner.medium, "medium"),
             Difficulty::Hard => (&self.inner.hard, "hard"),
-        };
-        convert_game(def, label)
+        }
     }
 }
 
-pub fn fetch_puzzle(date: NaiveDate) -> Result<NytPuzzle, String> {
+pub fn fetch_puzzle(date: NaiveDate) -> Result<NytPuzzle, NytError> {
     let json = fetch_puzzle_json(date)?;
-    NytPuzzle::from_json(&json)
+    Ok(NytPuzzle::from_json(&json)?)
 }
 
-pub fn fetch_puzzle_json(date: NaiveDate) -> Result<String, String> {
+pub fn fetch_puzzle_json(date: NaiveDate) -> Result<String, NytError> {
     if let Ok(dir) = env::var("NYT_PIPS_JSON_DIR") {
         if !dir.trim().is_empty() {
             return read_from_directory(PathBuf::from(dir), date);
@@ -101,7 +160,7 @@ pub fn fetch_puzzle_json(date: NaiveDate) -> Result<String, String> {
     fetch_from_base(base.trim(), date)
 }
 
-fn fetch_from_base(base: &str, date: NaiveDate) -> Result<String, String> {
+fn fetch_from_base(base: &str, date: NaiveDate) -> Result<String, NytError> {
     if base.starts_with("file://") {
         let path = &base["file://".len()..];
         return read_from_directory(PathBuf::from(path), date);
@@ -115,28 +174,130 @@ fn fetch_from_base(base: &str, date: NaiveDate) -> Result<String, String> {
     fetch_remote(base, date)
 }
 
-fn read_from_directory(directory: PathBuf, date: NaiveDate) -> Result<String, String> {
-    let mut path = directory;
-    path.push(format!("game-{}.json", date.format("%Y-%m-%d")));
-    std::fs::read_to_string(&path)
-        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+/// Reads `game-DATE.json` from `directory`, falling back to a gzip-compressed
+/// `game-DATE.json.gz` archive when the plain file is absent.
+fn read_from_directory(directory: PathBuf, date: NaiveDate) -> Result<String, NytError> {
+    let path = directory.join(format!("game-{}.json", date.format("%Y-%m-%d")));
+    match std::fs::read_to_string(&path) {
+        Ok(json) => return Ok(json),
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            return Err(NytError::Other(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                err
+            )));
+        }
+        Err(_) => {}
+    }
+
+    let gz_path = directory.join(format!("game-{}.json.gz", date.format("%Y-%m-%d")));
+    let file = std::fs::File::open(&gz_path).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            NytError::NotPublished(date)
+        } else {
+            NytError::Other(format!("Failed to read {}: {}", gz_path.display(), err))
+        }
+    })?;
+    let mut json = String::new();
+    GzDecoder::new(file)
+        .read_to_string(&mut json)
+        .map_err(|err| {
+            NytError::Other(format!(
+                "Failed to decompress {}: {}",
+                gz_path.display(),
+                err
+            ))
+        })?;
+    Ok(json)
 }
 
-fn fetch_remote(base_url: &str, date: NaiveDate) -> Result<String, String> {
+fn fetch_remote(base_url: &str, date: NaiveDate) -> Result<String, NytError> {
     let normalized = base_url.trim_end_matches('/');
     let url = format!("{}/{}.json", normalized, date.format("%Y-%m-%d"));
     match ureq::get(&url).call() {
-        Ok(response) => response
-            .into_string()
-            .map_err(|err| format!("Failed to read response from {}: {}", url, err)),
-        Err(UreqError::Status(code, _)) => {
-            Err(format!("NYTimes returned HTTP {} for {}.", code, url))
-        }
-        Err(UreqError::Transport(err)) => Err(format!("Request to {} failed: {}", url, err)),
+        Ok(response) => response.into_string().map_err(|err| {
+            NytError::Other(format!("Failed to read response from {}: {}", url, err))
+        }),
+        Err(UreqError::Status(404, _)) => Err(NytError::NotPublished(date)),
+        Err(UreqError::Status(code, _)) => Err(NytError::Other(format!(
+            "NYTimes returned HTTP {} for {}.",
+            code, url
+        ))),
+        Err(UreqError::Transport(err)) => Err(NytError::Other(format!(
+            "Request to {} failed: {}",
+            url, err
+        ))),
     }
 }
 
+/// Writes `game` as NYT puzzle JSON under the `difficulty` key: `dominoes` as pip pairs and
+/// one region per constraint, with `[row, column]` indices. Cells no constraint covers become
+/// single-cell `empty` regions so the board survives. Only domino games whose constraints
+/// have an NYT counterpart can be exported.
+pub fn to_nyt_json(game: &Game, difficulty: Difficulty) -> Result<String, String> {
+    let dominoes = game
+        .pieces
+        .iter()
+        .map(|piece| match piece.pips() {
+            [a, b] if piece.shape() == PolyShape::Domino => Ok([a.value(), b.value()]),
+            _ => Err(format!(
+                "NYT puzzles only have dominoes, but the game has a {}.",
+                piece.shape().name()
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let indices = |points: &HashSet<Point>| -> Vec<[u32; 2]> {
+        let mut sorted: Vec<Point> = points.iter().copied().collect();
+        sorted.sort_by_key(|point| (point.y, point.x));
+        sorted.into_iter().map(|point| [point.y, point.x]).collect()
+    };
+    let mut regions = Vec::new();
+    let mut covered = HashSet::new();
+    for constraint in &game.constraints {
+        let (kind, target) = match constraint {
+            Constraint::AllSame { expected: None, .. } => ("equals", None),
+            Constraint::AllDifferent { excluded, .. } if excluded.is_empty() => ("unequal", None),
+            Constraint::Exactly { target, .. } => ("sum", Some(*target)),
+            Constraint::MoreThan { target, .. } => ("greater", Some(*target)),
+            Constraint::LessThan { target, .. } => ("less", Some(*target)),
+            other => return Err(format!("Constraint {} has no NYT region type.", other)),
+        };
+        covered.extend(constraint.points().iter().copied());
+        regions.push(Region {
+            indices: indices(constraint.points()),
+            target,
+            kind: kind.to_string(),
+        });
+    }
+    let mut open: Vec<Point> = game
+        .board
+        .iter()
+        .filter(|point| !covered.contains(point))
+        .collect();
+    open.sort_by_key(|point| (point.y, point.x));
+    regions.extend(open.into_iter().map(|point| Region {
+        indices: vec![[point.y, point.x]],
+        target: None,
+        kind: "empty".to_string(),
+    }));
+
+    let def = GameDef {
+        constructors: None,
+        dominoes,
+        regions,
+        id: None,
+    };
+    let file = BTreeMap::from([(difficulty.as_str(), def)]);
+    serde_json::to_string_pretty(&file).map_err(|err| format!("Failed to encode puzzle: {}", err))
+}
+
 fn convert_game(game: &GameDef, label: &str) -> Result<Game, String> {
+    convert_game_verbose(game, label).map(|(game, _)| game)
+}
+
+fn convert_game_verbose(game: &GameDef, label: &str) -> Result<(Game, Vec<String>), String> {
+    let mut log = Vec::new();
     let board_points: BTreeSet<(u32, u32)> = game
         .regions
         .iter()
@@ -193,8 +354,16 @@ fn convert_game(game: &GameDef, label: &str) -> Result<Game, String> {
     output.push('\n');
 
     output.push_str("constraints:\n");
-    for region in &game.regions {
+    for (index, region) in game.regions.iter().enumerate() {
+        let target_text = region
+            .target
+            .map(|target| target.to_string())
+            .unwrap_or_else(|| "none".to_string());
         if region.kind == "empty" {
+            log.push(format!(
+                "region {}: type 'empty' target {} -> skipped",
+                index, target_text
+            ));
             continue;
         }
         let mut points: Vec<(u32, u32)> =
@@ -239,19 +408,30 @@ fn convert_game(game: &GameDef, label: &str) -> Result<Game, String> {
                 ));
             }
         };
+        log.push(format!(
+            "region {}: type '{}' target {} -> {}",
+            index, region.kind, target_text, text
+        ));
         output.push_str(&text);
         output.push('\n');
     }
     output.push('\n');
 
-    load_game_from_reader(Cursor::new(output))
+    load_game_from_reader(Cursor::new(output)).map(|game| (game, log))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Difficulty, NytPuzzle, fetch_puzzle_json};
+    use super::{
+        Difficulty, GameDef, NytError, NytPuzzle, convert_game, fetch_from_base, fetch_puzzle_json,
+        to_nyt_json,
+    };
     use chrono::NaiveDate;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
     use std::fs;
+    use std::io::Write;
+    use std::sync::{Mutex, MutexGuard};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     const SAMPLE_JSON: &str = r#"
@@ -296,6 +476,18 @@ mod tests {
         assert_eq!(hard.pieces.len(), 1);
     }
 
+    #[test]
+    fn verbose_conversion_logs_each_region() {
+        let puzzle = NytPuzzle::from_json(SAMPLE_JSON).expect("puzzle parses");
+        let (game, log) = puzzle
+            .game_verbose(Difficulty::Hard)
+            .expect("hard game converts");
+        assert_eq!(game.constraints.len(), 2);
+        assert_eq!(log.len(), 2);
+        assert!(log[0].contains("'greater'") && log[0].contains("-> MoreThan 4 {(0,0)}"));
+        assert!(log[1].contains("'less'") && log[1].contains("-> LessThan 6 {(0,1)}"));
+    }
+
     #[test]
     fn fetch_prefers_json_directory_env() {
         let timestamp = SystemTime::now()
@@ -318,20 +510,56 @@ mod tests {
         assert!(json.contains("\"easy\""));
     }
 
+    #[test]
+    fn fetch_decompresses_gzipped_archive() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time ok")
+            .as_nanos();
+        let temp_dir = std::env::temp_dir().join(format!("pips_nyt_gz_{}", timestamp));
+        fs::create_dir(&temp_dir).expect("create temp dir");
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let file_path = temp_dir.join("game-2025-01-02.json.gz");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(SAMPLE_JSON.as_bytes())
+            .expect("compress sample");
+        fs::write(&file_path, encoder.finish().expect("finish gzip")).expect("write sample");
+
+        let guard = EnvGuard::set("NYT_PIPS_JSON_DIR", &temp_dir);
+        let json = fetch_puzzle_json(date);
+        drop(guard);
+
+        fs::remove_file(&file_path).ok();
+        fs::remove_dir(&temp_dir).ok();
+        assert_eq!(json.expect("fetch gz from dir"), SAMPLE_JSON);
+    }
+
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
     struct EnvGuard {
         key: &'static str,
         previous: Option<String>,
+        _lock: MutexGuard<'static, ()>,
     }
 
     impl EnvGuard {
         fn set<T: AsRef<std::ffi::OsStr>>(key: &'static str, value: T) -> Self {
+            let lock = ENV_LOCK
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
             let previous = std::env::var(key).ok();
             // Safety: these tests run in process isolation, and we restore the
             // previous value (if any) before the guard drops.
             unsafe {
                 std::env::set_var(key, value);
             }
-            Self { key, previous }
+            Self {
+                key,
+                previous,
+                _lock: lock,
+            }
         }
     }
 
@@ -348,4 +576,77 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn difficulty_parses_names_and_initials() {
+        for (token, expected) in [
+            ("easy", "easy"),
+            ("Easy", "easy"),
+            ("e", "easy"),
+            ("MEDIUM", "medium"),
+            ("m", "medium"),
+            ("hard", "hard"),
+            ("H", "hard"),
+        ] {
+            let difficulty: Difficulty = token.parse().unwrap();
+            assert_eq!(difficulty.as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn difficulty_rejects_unknown_values() {
+        let err = "extreme".parse::<Difficulty>().unwrap_err();
+        assert!(err.contains("extreme"));
+        assert!("all".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn missing_puzzle_file_reports_not_published() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time ok")
+            .as_nanos();
+        let temp_dir = std::env::temp_dir().join(format!("pips_nyt_missing_{}", timestamp));
+        fs::create_dir(&temp_dir).expect("create temp dir");
+
+        let date = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let base = format!("file://{}", temp_dir.display());
+        let err = fetch_from_base(&base, date).unwrap_err();
+        fs::remove_dir(&temp_dir).ok();
+
+        assert_eq!(err, NytError::NotPublished(date));
+        assert_eq!(err.to_string(), "No puzzle available for 2030-01-01 yet.");
+    }
+
+    #[test]
+    fn exported_json_converts_back_to_the_same_game() {
+        let puzzle = NytPuzzle::from_json(SAMPLE_JSON).expect("puzzle parses");
+        let easy = puzzle.game(Difficulty::Easy).expect("easy game");
+        let json = to_nyt_json(&easy, Difficulty::Easy).expect("domino game exports");
+
+        let value: serde_json::Value = serde_json::from_str(&json).expect("valid JSON");
+        let def: GameDef = serde_json::from_value(value["easy"].clone()).expect("game def");
+        assert_eq!(def.dominoes, vec![[1, 2], [2, 3]]);
+        let kinds: Vec<&str> = def
+            .regions
+            .iter()
+            .map(|region| region.kind.as_str())
+            .collect();
+        assert_eq!(kinds, ["sum", "equals"]);
+        assert_eq!(def.regions[0].indices, vec![[0, 0], [1, 0]]);
+        assert_eq!(def.regions[0].target, Some(5));
+
+        let restored = convert_game(&def, "easy").expect("exported game converts");
+        assert!(restored.structurally_eq(&easy));
+    }
+
+    #[test]
+    fn export_rejects_non_domino_pieces() {
+        let game = crate::loader::load_game_from_reader(std::io::Cursor::new(
+            "board:\n###\n\npieces:\n3I:123\n",
+        ))
+        .expect("tromino game loads");
+        let err = to_nyt_json(&game, Difficulty::Hard).unwrap_err();
+        assert!(err.contains("only have dominoes"), "{}", err);
+    }
 }
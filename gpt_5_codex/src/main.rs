@@ -1,11 +1,25 @@
+use pips_solver::model::{Game, Placement};
 use pips_solver::{display, loader, solver};
 use std::env;
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// Exit status when the solver gives up at the `--timeout` deadline.
+const TIMEOUT_EXIT_CODE: i32 = 2;
+
+/// Minimum gap between `--progress` status lines.
+const PROGRESS_PERIOD: Duration = Duration::from_secs(1);
 
 struct CliOptions {
     show_game: bool,
+    show_legend: bool,
     show_playout: bool,
+    show_pivot: bool,
+    validate_only: bool,
+    show_progress: bool,
+    drop_trivial: bool,
+    repeat: usize,
+    timeout_secs: f64,
     path: String,
 }
 
@@ -18,7 +32,24 @@ fn main() {
 
 fn run() -> Result<(), String> {
     let options = parse_args()?;
-    let game = loader::load_game_from_path(&options.path)?;
+    let load_options = loader::LoadOptions {
+        drop_trivial_constraints: options.drop_trivial,
+    };
+    let report = loader::load_game_from_path_with(&options.path, &load_options)?;
+    for constraint in &report.dropped_constraints {
+        eprintln!(
+            "Warning: dropping always-satisfied constraint {}",
+            constraint
+        );
+    }
+    let game = report.game;
+
+    if options.validate_only {
+        game.validate()?;
+        println!("OK");
+        println!("{} cells, {} pieces", game.board.len(), game.pieces.len());
+        return Ok(());
+    }
 
     if options.show_game {
         let unsolved = display::render_unsolved(&game);
@@ -26,7 +57,14 @@ fn run() -> Result<(), String> {
             for line in &unsolved {
                 println!("{}", line);
             }
-            let piece_lines = display::render_dominoes(&game.pieces);
+            if options.show_legend {
+                println!("\nLegend:\n");
+                for line in display::render_legend(&game) {
+                    println!("{}", line);
+                }
+            }
+            let piece_lines =
+                display::render_domino_inventory(&game.pieces, display::terminal_width());
             if !piece_lines.is_empty() {
                 println!("\nPieces:\n");
                 for line in piece_lines {
@@ -37,36 +75,135 @@ fn run() -> Result<(), String> {
         }
     }
 
-    let started = Instant::now();
-    let placements = solver::solve(&game)?;
-    let elapsed = started.elapsed();
+    if options.show_pivot {
+        match game.pivot_point() {
+            Some(pivot) => {
+                println!("Pivot cell {}:\n", pivot);
+                for line in display::render_with_marker(&game.board, pivot) {
+                    println!("{}", line);
+                }
+            }
+            None => println!("No pivot cell: the board is empty."),
+        }
+        println!();
+    }
+
+    let mut timings = Vec::with_capacity(options.repeat);
+    let mut placements = Vec::new();
+    for _ in 0..options.repeat {
+        let (solution, elapsed) = solve_once(&game, &options)?;
+        placements = solution;
+        timings.push(elapsed);
+    }
+    let elapsed = timings[timings.len() - 1];
 
     if options.show_playout {
         println!("Playout:\n");
-        for (index, placement) in placements.iter().enumerate() {
-            println!("{}: {}", index + 1, placement);
+        for line in display::render_playout(&placements, display::color_enabled()) {
+            println!("{}", line);
         }
         println!();
     }
 
     println!("Found a solution in {:?}", elapsed);
     println!();
-    let rendered = display::render_solution(&game, &placements);
+    let rendered = display::render_solution_colored(&game, &placements, display::color_enabled());
     for line in rendered {
         println!("{}", line);
     }
+
+    if options.repeat > 1 {
+        timings.sort();
+        println!();
+        println!("Timing over {} runs:", timings.len());
+        println!("  min:    {:?}", timings[0]);
+        println!("  median: {:?}", timings[timings.len() / 2]);
+        println!("  max:    {:?}", timings[timings.len() - 1]);
+    }
     Ok(())
 }
 
+/// One timed solve under the `--timeout` deadline; exits the process if the deadline passes.
+fn solve_once(game: &Game, options: &CliOptions) -> Result<(Vec<Placement>, Duration), String> {
+    let started = Instant::now();
+    let deadline = Duration::try_from_secs_f64(options.timeout_secs)
+        .ok()
+        .and_then(|timeout| started.checked_add(timeout))
+        .ok_or_else(|| format!("Invalid --timeout value '{}'.", options.timeout_secs))?;
+    let mut explored = 0;
+    let mut last_report = started;
+    let outcome = solver::solve_with_progress(game, deadline, &mut |nodes| {
+        explored = nodes;
+        if options.show_progress && last_report.elapsed() >= PROGRESS_PERIOD {
+            eprintln!("... {} nodes explored", format_count(nodes));
+            last_report = Instant::now();
+        }
+    });
+    if options.show_progress {
+        eprintln!("{} nodes explored in total", format_count(explored));
+    }
+    let Some(placements) = outcome? else {
+        println!("Timed out after {}s", options.timeout_secs);
+        process::exit(TIMEOUT_EXIT_CODE);
+    };
+    Ok((placements, started.elapsed()))
+}
+
+/// Compact node counts for `--progress` status lines, e.g. `1.2M`.
+fn format_count(count: usize) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}K", count as f64 / 1e3),
+        _ => format!("{:.1}M", count as f64 / 1e6),
+    }
+}
+
 fn parse_args() -> Result<CliOptions, String> {
     let mut show_game = false;
+    let mut show_legend = false;
     let mut show_playout = false;
+    let mut show_pivot = false;
+    let mut validate_only = false;
+    let mut show_progress = false;
+    let mut drop_trivial = false;
+    let mut repeat = 1;
+    let mut timeout_secs = 60.0;
     let mut positional = Vec::new();
 
-    for arg in env::args().skip(1) {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
         match arg.as_str() {
             "--show-game" => show_game = true,
+            "--show-legend" => show_legend = true,
             "--show-playout" => show_playout = true,
+            "--show-pivot" => show_pivot = true,
+            "--validate-only" => validate_only = true,
+            "--progress" => show_progress = true,
+            "--drop-trivial" => drop_trivial = true,
+            "--timeout" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--timeout requires a number of seconds.".to_string())?;
+                // Reject timeouts too large to add to the clock rather than panic mid-run.
+                timeout_secs = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| {
+                        Duration::try_from_secs_f64(*secs)
+                            .is_ok_and(|timeout| Instant::now().checked_add(timeout).is_some())
+                    })
+                    .ok_or_else(|| format!("Invalid --timeout value '{}'.", value))?;
+            }
+            "--repeat" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "--repeat requires a number of runs.".to_string())?;
+                repeat = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|runs| *runs > 0)
+                    .ok_or_else(|| format!("Invalid --repeat value '{}'.", value))?;
+            }
             other if other.starts_with("--") => {
                 return Err(format!("Unknown flag '{}'.", other));
             }
@@ -76,13 +213,21 @@ fn parse_args() -> Result<CliOptions, String> {
 
     if positional.len() != 1 {
         return Err(
-            "Usage: pips-solver [--show-game] [--show-playout] <path-to-game-file>".to_string(),
+            "Usage: pips-solver [--show-game [--show-legend]] [--show-playout] [--show-pivot] [--validate-only] [--progress] [--drop-trivial] [--repeat <runs>] [--timeout <seconds>] <path-to-game-file>"
+                .to_string(),
         );
     }
 
     Ok(CliOptions {
         show_game,
+        show_legend,
         show_playout,
+        show_pivot,
+        validate_only,
+        show_progress,
+        drop_trivial,
+        repeat,
+        timeout_secs,
         path: positional.remove(0),
     })
 }
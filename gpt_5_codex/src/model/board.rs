@@ -1,4 +1,5 @@
 use super::point::Point;
+use crate::util::rng::SimpleRng;
 use once_cell::sync::Lazy;
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -157,13 +158,57 @@ impl Board {
         indices.iter().all(|&index| self.storage.test_bit(index))
     }
 
+    /// Bulk form of `contains_indices`: builds a mask per bitset word and tests each word
+    /// once. Fastest when `indices` is sorted, as catalog rows are.
+    pub fn all_indices_set(&self, indices: &[usize]) -> bool {
+        let bits = &self.storage.bits;
+        let mut current_word = usize::MAX;
+        let mut mask = 0u64;
+        for &index in indices {
+            let (word, offset) = self.storage.bit_index(index);
+            if word != current_word {
+                if mask != 0 && bits[current_word] & mask != mask {
+                    return false;
+                }
+                if word >= bits.len() {
+                    return false;
+                }
+                current_word = word;
+                mask = 0;
+            }
+            mask |= 1u64 << offset;
+        }
+        mask == 0 || bits[current_word] & mask == mask
+    }
+
+    /// Sorted bitset indices of the cells `offsets` covers from `anchor`, or `None` unless every
+    /// one of them is on the board.
+    pub fn placement_indices(&self, anchor: Point, offsets: &[(i32, i32)]) -> Option<Vec<usize>> {
+        let mut indices = Vec::with_capacity(offsets.len());
+        for &(dx, dy) in offsets {
+            let x = u32::try_from(anchor.x as i32 + dx).ok()?;
+            let y = u32::try_from(anchor.y as i32 + dy).ok()?;
+            indices.push(self.index_of(&Point::new(x, y))?);
+        }
+        indices.sort_unstable();
+        self.all_indices_set(&indices).then_some(indices)
+    }
+
     pub fn total_cells(&self) -> usize {
         self.storage.total_cells()
     }
 
     pub fn remove_points(&self, to_remove: &[Point]) -> Result<Self, String> {
-        if !self.contains_all(to_remove) {
-            return Err("Placement has at least one point outside of the board.".to_string());
+        let outside: Vec<String> = to_remove
+            .iter()
+            .filter(|point| !self.contains_point(point))
+            .map(Point::to_string)
+            .collect();
+        if !outside.is_empty() {
+            return Err(format!(
+                "Placement covers {} outside of the board.",
+                outside.join(", ")
+            ));
         }
 
         let mut storage = Arc::clone(&self.storage);
@@ -176,6 +221,77 @@ impl Board {
         Ok(Board { storage })
     }
 
+    /// The same board shifted by `(dx, dy)`. Fails if any cell would leave the `u32` grid.
+    pub fn translate(&self, dx: i32, dy: i32) -> Result<Board, String> {
+        if self.is_empty() {
+            return Ok(self.clone());
+        }
+        let shift = |min: u32, extent: u32, delta: i32| {
+            let start = u32::try_from(min as i64 + delta as i64).ok()?;
+            start.checked_add(extent - 1).map(|_| start)
+        };
+        let (Some(min_x), Some(min_y)) = (
+            shift(self.storage.min_x, self.storage.width, dx),
+            shift(self.storage.min_y, self.storage.height, dy),
+        ) else {
+            return Err(format!(
+                "Cannot translate board by ({}, {}): coordinates would leave the grid.",
+                dx, dy
+            ));
+        };
+        let mut storage = (*self.storage).clone();
+        storage.min_x = min_x;
+        storage.min_y = min_y;
+        Ok(Self {
+            storage: Arc::new(storage),
+        })
+    }
+
+    /// A connected board of `target_cells` cells grown from `seed` by repeatedly adding a
+    /// random orthogonal neighbor of the cells added so far. Zero cells gives an empty board.
+    pub fn grow_random(seed: Point, target_cells: usize, rng: &mut SimpleRng) -> Board {
+        let mut cells = HashSet::new();
+        if target_cells == 0 {
+            return Board::new(cells);
+        }
+        cells.insert(seed);
+        let mut frontier: Vec<Point> = orthogonal_neighbors(seed);
+        let mut queued: HashSet<Point> = frontier.iter().copied().collect();
+        while cells.len() < target_cells && !frontier.is_empty() {
+            let pick = rng.gen_range_usize(0, frontier.len() - 1);
+            let point = frontier.swap_remove(pick);
+            cells.insert(point);
+            for neighbor in orthogonal_neighbors(point) {
+                if !cells.contains(&neighbor) && queued.insert(neighbor) {
+                    frontier.push(neighbor);
+                }
+            }
+        }
+        Board::new(cells)
+    }
+
+    /// True if `other` is this board shifted by some offset, ignoring rotation and reflection.
+    pub fn same_shape(&self, other: &Board) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+        // Row-major order survives translation, so the first cells must correspond.
+        let (Some(first), Some(other_first)) = (self.iter().next(), other.iter().next()) else {
+            return true;
+        };
+        let dx = other_first.x as i64 - first.x as i64;
+        let dy = other_first.y as i64 - first.y as i64;
+        self.iter().all(|point| {
+            match (
+                u32::try_from(point.x as i64 + dx),
+                u32::try_from(point.y as i64 + dy),
+            ) {
+                (Ok(x), Ok(y)) => other.contains_point(&Point::new(x, y)),
+                _ => false,
+            }
+        })
+    }
+
     pub fn iter(&self) -> BoardIter<'_> {
         BoardIter {
             storage: &self.storage,
@@ -183,9 +299,160 @@ impl Board {
         }
     }
 
+    /// Like `iter`, but pairs each cell with its bitset index, the same index `index_of`
+    /// returns. Indices skip holes, so they are not a dense `0..len()` range.
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, Point)> + '_ {
+        let mut iter = self.iter();
+        std::iter::from_fn(move || iter.next_indexed())
+    }
+
     pub fn to_hash_set(&self) -> HashSet<Point> {
         self.iter().collect()
     }
+
+    /// Cells with fewer than four orthogonal neighbors on the board.
+    pub fn perimeter_cells(&self) -> HashSet<Point> {
+        self.iter()
+            .filter(|point| self.neighbor_count(*point) < 4)
+            .collect()
+    }
+
+    /// Cells whose four orthogonal neighbors are all on the board.
+    pub fn interior_cells(&self) -> HashSet<Point> {
+        self.iter()
+            .filter(|point| self.neighbor_count(*point) == 4)
+            .collect()
+    }
+
+    /// Rotations and reflections that map the board's (normalized) shape onto itself.
+    pub fn symmetries(&self) -> SymmetrySet {
+        let cells: Vec<(i64, i64)> = self
+            .iter()
+            .map(|point| (point.x as i64, point.y as i64))
+            .collect();
+        let original = normalized_cells(cells.iter().copied());
+        let mut set = SymmetrySet::default();
+        for symmetry in Symmetry::ALL {
+            let transformed = normalized_cells(cells.iter().map(|&(x, y)| symmetry.apply(x, y)));
+            if transformed == original {
+                set.insert(symmetry);
+            }
+        }
+        set
+    }
+
+    fn neighbor_count(&self, point: Point) -> usize {
+        orthogonal_neighbors(point)
+            .iter()
+            .filter(|neighbor| self.contains_point(neighbor))
+            .count()
+    }
+}
+
+/// A non-identity element of the square's dihedral group, in screen coordinates (y down).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Symmetry {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirror left-to-right.
+    ReflectHorizontal,
+    /// Mirror top-to-bottom.
+    ReflectVertical,
+    /// Mirror across the main (top-left to bottom-right) diagonal.
+    ReflectDiagonal,
+    /// Mirror across the anti-diagonal.
+    ReflectAntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 7] = [
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::ReflectHorizontal,
+        Symmetry::ReflectVertical,
+        Symmetry::ReflectDiagonal,
+        Symmetry::ReflectAntiDiagonal,
+    ];
+
+    fn apply(self, x: i64, y: i64) -> (i64, i64) {
+        match self {
+            Symmetry::Rotate90 => (-y, x),
+            Symmetry::Rotate180 => (-x, -y),
+            Symmetry::Rotate270 => (y, -x),
+            Symmetry::ReflectHorizontal => (-x, y),
+            Symmetry::ReflectVertical => (x, -y),
+            Symmetry::ReflectDiagonal => (y, x),
+            Symmetry::ReflectAntiDiagonal => (-y, -x),
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << Symmetry::ALL.iter().position(|&s| s == self).unwrap()
+    }
+}
+
+/// The symmetries a board has, beyond the identity.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SymmetrySet {
+    bits: u8,
+}
+
+impl SymmetrySet {
+    pub fn insert(&mut self, symmetry: Symmetry) {
+        self.bits |= symmetry.bit();
+    }
+
+    pub fn contains(&self, symmetry: Symmetry) -> bool {
+        self.bits & symmetry.bit() != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// True when every rotation and reflection of the square applies (the full D4 group).
+    pub fn is_full(&self) -> bool {
+        self.len() == Symmetry::ALL.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Symmetry> + '_ {
+        Symmetry::ALL
+            .into_iter()
+            .filter(move |symmetry| self.contains(*symmetry))
+    }
+}
+
+fn normalized_cells(cells: impl Iterator<Item = (i64, i64)>) -> Vec<(i64, i64)> {
+    let cells: Vec<(i64, i64)> = cells.collect();
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    let mut normalized: Vec<(i64, i64)> =
+        cells.iter().map(|&(x, y)| (x - min_x, y - min_y)).collect();
+    normalized.sort();
+    normalized
+}
+
+pub(crate) fn orthogonal_neighbors(point: Point) -> Vec<Point> {
+    let mut neighbors = Vec::with_capacity(4);
+    if let Some(x) = point.x.checked_sub(1) {
+        neighbors.push(Point::new(x, point.y));
+    }
+    if let Some(x) = point.x.checked_add(1) {
+        neighbors.push(Point::new(x, point.y));
+    }
+    if let Some(y) = point.y.checked_sub(1) {
+        neighbors.push(Point::new(point.x, y));
+    }
+    if let Some(y) = point.y.checked_add(1) {
+        neighbors.push(Point::new(point.x, y));
+    }
+    neighbors
 }
 
 impl BoardStorage {
@@ -211,10 +478,8 @@ pub struct BoardIter<'a> {
     index: usize,
 }
 
-impl<'a> Iterator for BoardIter<'a> {
-    type Item = Point;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl BoardIter<'_> {
+    fn next_indexed(&mut self) -> Option<(usize, Point)> {
         let total = self.storage.total_cells();
         while self.index < total {
             let current = self.index;
@@ -225,13 +490,21 @@ impl<'a> Iterator for BoardIter<'a> {
                 let rel_x = current % width;
                 let x = self.storage.min_x + rel_x as u32;
                 let y = self.storage.min_y + rel_y as u32;
-                return Some(Point::new(x, y));
+                return Some((current, Point::new(x, y)));
             }
         }
         None
     }
 }
 
+impl<'a> Iterator for BoardIter<'a> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_indexed().map(|(_, point)| point)
+    }
+}
+
 impl Default for Board {
     fn default() -> Self {
         Self {
@@ -245,9 +518,22 @@ pub static EMPTY_BOARD: Lazy<Board> = Lazy::new(Board::default);
 
 #[cfg(test)]
 mod tests {
-    use super::{Board, Point};
+    use super::{Board, Point, Symmetry, orthogonal_neighbors};
+    use crate::model::{Piece, Pips, Placement};
+    use crate::util::rng::SimpleRng;
     use std::collections::HashSet;
 
+    #[test]
+    fn remove_points_names_off_board_cells() {
+        let board = Board::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect());
+        let piece = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let pips = piece.pips().to_vec();
+        // Orientation 1 is vertical, so the second cell hangs below the one-row board.
+        let placement = Placement::new(piece, Point::new(1, 0), 1, pips);
+        let err = board.remove_points(&placement.points()).unwrap_err();
+        assert_eq!(err, "Placement covers (1, 1) outside of the board.");
+    }
+
     #[test]
     fn remove_points_succeeds_for_subset() {
         let mut pts = HashSet::new();
@@ -261,10 +547,189 @@ mod tests {
         assert!(next.contains_point(&Point::new(0, 0)));
     }
 
+    #[test]
+    fn classifies_perimeter_and_interior_of_square() {
+        let mut pts = HashSet::new();
+        for y in 0..3 {
+            for x in 0..3 {
+                pts.insert(Point::new(x, y));
+            }
+        }
+        let board = Board::new(pts);
+
+        let interior = board.interior_cells();
+        assert_eq!(interior.len(), 1);
+        assert!(interior.contains(&Point::new(1, 1)));
+
+        let perimeter = board.perimeter_cells();
+        assert_eq!(perimeter.len(), 8);
+        assert!(!perimeter.contains(&Point::new(1, 1)));
+    }
+
     #[test]
     fn remove_points_errors_for_non_subset() {
         let board = Board::default();
         let take = [Point::new(0, 0)];
         assert!(board.remove_points(&take).is_err());
     }
+
+    fn board_from(coords: &[(u32, u32)]) -> Board {
+        Board::new(coords.iter().map(|&(x, y)| Point::new(x, y)).collect())
+    }
+
+    #[test]
+    fn square_and_plus_boards_have_full_symmetry() {
+        let square = board_from(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        assert!(square.symmetries().is_full());
+
+        let plus = board_from(&[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]);
+        assert!(plus.symmetries().is_full());
+    }
+
+    #[test]
+    fn l_shaped_board_has_no_symmetry() {
+        let ell = board_from(&[(0, 0), (0, 1), (0, 2), (1, 2)]);
+        assert!(ell.symmetries().is_empty());
+    }
+
+    #[test]
+    fn rectangle_keeps_only_half_turn_and_axis_mirrors() {
+        let rectangle = board_from(&[(3, 5), (4, 5), (5, 5), (3, 6), (4, 6), (5, 6)]);
+        let symmetries = rectangle.symmetries();
+        assert_eq!(symmetries.len(), 3);
+        assert!(symmetries.contains(Symmetry::Rotate180));
+        assert!(symmetries.contains(Symmetry::ReflectHorizontal));
+        assert!(symmetries.contains(Symmetry::ReflectVertical));
+    }
+
+    #[test]
+    fn translate_shifts_every_cell() {
+        let board = board_from(&[(0, 0), (1, 0), (1, 1)]);
+        let moved = board.translate(2, 3).unwrap();
+        assert_eq!(moved, board_from(&[(2, 3), (3, 3), (3, 4)]));
+        assert_eq!(moved.translate(-2, -3).unwrap(), board);
+    }
+
+    #[test]
+    fn grow_random_reaches_target_as_one_component() {
+        let mut rng = SimpleRng::new(Some(2450), 0, 0);
+        for target in [1, 2, 7, 30] {
+            let seed = Point::new(3, 0);
+            let board = Board::grow_random(seed, target, &mut rng);
+            assert_eq!(board.len(), target);
+            assert!(board.contains_point(&seed));
+
+            let mut reached = HashSet::from([seed]);
+            let mut stack = vec![seed];
+            while let Some(point) = stack.pop() {
+                for neighbor in orthogonal_neighbors(point) {
+                    if board.contains_point(&neighbor) && reached.insert(neighbor) {
+                        stack.push(neighbor);
+                    }
+                }
+            }
+            assert_eq!(reached.len(), target);
+        }
+        assert!(Board::grow_random(Point::new(0, 0), 0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn same_shape_ignores_translation() {
+        let board = board_from(&[(0, 0), (1, 0), (1, 1)]);
+        let moved = board.translate(3, 3).unwrap();
+        assert!(board.same_shape(&moved));
+        assert!(moved.same_shape(&board));
+        assert_ne!(board, moved);
+
+        let trimmed = board_from(&[(0, 0), (1, 0), (1, 1), (2, 1)])
+            .remove_points(&[Point::new(2, 1)])
+            .unwrap();
+        assert!(trimmed.same_shape(&moved));
+        assert!(!board.same_shape(&board_from(&[(0, 0), (1, 0), (0, 1)])));
+        assert!(!board.same_shape(&board_from(&[(0, 0), (1, 0)])));
+    }
+
+    #[test]
+    fn translate_rejects_negative_coordinates() {
+        let board = board_from(&[(1, 0), (2, 0)]);
+        assert!(board.translate(-2, 0).is_err());
+        assert!(board.translate(0, -1).is_err());
+        assert!(board.translate(-1, 0).is_ok());
+    }
+
+    #[test]
+    fn all_indices_set_matches_per_index_check() {
+        let mut pts = HashSet::new();
+        for y in 0..9 {
+            for x in 0..9 {
+                if (x * 7 + y * 3) % 5 != 0 {
+                    pts.insert(Point::new(x, y));
+                }
+            }
+        }
+        let board = Board::new(pts);
+        let total = board.total_cells();
+        for start in 0..total {
+            for len in 1..=5 {
+                for stride in [1, 8, 9, 40] {
+                    let indices: Vec<usize> = (0..len)
+                        .map(|step| start + step * stride)
+                        .filter(|&index| index < total)
+                        .collect();
+                    assert_eq!(
+                        board.all_indices_set(&indices),
+                        board.contains_indices(&indices),
+                        "{:?}",
+                        indices
+                    );
+                }
+            }
+        }
+        assert!(board.all_indices_set(&[]));
+        assert!(!board.all_indices_set(&[total + 64]));
+    }
+
+    #[test]
+    fn placement_indices_require_every_cell_on_the_board() {
+        let board = board_from(&[(0, 0), (1, 0), (2, 0), (0, 1), (2, 1)]);
+        let bar = [(0, 0), (1, 0), (2, 0)];
+        let expected: Vec<usize> = bar
+            .iter()
+            .map(|&(x, y)| board.index_of(&Point::new(x, y)).unwrap())
+            .collect();
+        assert_eq!(
+            board.placement_indices(Point::new(0, 0), &[(2, 0), (0, 0), (1, 0)]),
+            Some(expected)
+        );
+        // (1, 1) is a hole, (3, 0) is past the edge and (-1, 0) is off the grid.
+        assert_eq!(
+            board.placement_indices(Point::new(0, 1), &[(0, 0), (1, 0)]),
+            None
+        );
+        assert_eq!(
+            board.placement_indices(Point::new(2, 0), &[(0, 0), (1, 0)]),
+            None
+        );
+        assert_eq!(
+            board.placement_indices(Point::new(0, 0), &[(-1, 0), (0, 0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn iter_indexed_matches_index_of() {
+        let board = Board::new(
+            [(1, 1), (2, 1), (4, 1), (1, 2), (3, 3)]
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect(),
+        );
+        let indexed: Vec<(usize, Point)> = board.iter_indexed().collect();
+        assert_eq!(indexed.len(), board.len());
+        for &(index, point) in &indexed {
+            assert_eq!(board.index_of(&point), Some(index), "{}", point);
+        }
+        let points: Vec<Point> = indexed.iter().map(|&(_, point)| point).collect();
+        assert_eq!(points, board.iter().collect::<Vec<_>>());
+    }
 }
@@ -1,5 +1,5 @@
-use super::{assignment::Assignment, pips::Pips, placement::Placement, point::Point};
-use std::collections::HashSet;
+use super::{assignment::Assignment, board::Board, pips::Pips, placement::Placement, point::Point};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
@@ -29,7 +29,76 @@ pub enum Constraint {
 
 pub type ConstraintSet = Vec<Constraint>;
 
+/// Largest sum `cell_count` cells can reach, or an error if it does not fit in a `u32`.
+fn checked_max_sum(cell_count: usize) -> Result<u32, String> {
+    u32::try_from(cell_count)
+        .ok()
+        .and_then(|count| count.checked_mul(Pips::MAX as u32))
+        .ok_or_else(|| "Region too large for sum constraint.".to_string())
+}
+
+/// The exact value a `MoreThan` is left demanding when one cell remains and only the top pip
+/// `pip_max` clears `remaining_target`.
+fn forced_more_than(remaining_target: i64, remaining_points: usize, pip_max: u8) -> Option<u32> {
+    (remaining_points == 1 && remaining_target == pip_max as i64 - 1).then_some(pip_max as u32)
+}
+
+/// Like `checked_max_sum`, but saturating; only for regions that already passed validation.
+fn max_sum_for(cell_count: usize) -> u32 {
+    checked_max_sum(cell_count).unwrap_or(u32::MAX)
+}
+
+/// Collects `points` into a constraint's point set, rejecting repeated points.
+fn point_set(points: &[Point]) -> Result<Arc<HashSet<Point>>, String> {
+    let mut set = HashSet::with_capacity(points.len());
+    for point in points {
+        if !set.insert(*point) {
+            return Err(format!(
+                "Point {} appears more than once in the constraint.",
+                point
+            ));
+        }
+    }
+    Ok(Arc::new(set))
+}
+
 impl Constraint {
+    pub fn exactly_from(target: u32, points: &[Point]) -> Result<Constraint, String> {
+        Ok(Constraint::Exactly {
+            target,
+            points: point_set(points)?,
+        })
+    }
+
+    pub fn less_than_from(target: u32, points: &[Point]) -> Result<Constraint, String> {
+        Ok(Constraint::LessThan {
+            target,
+            points: point_set(points)?,
+        })
+    }
+
+    pub fn more_than_from(target: u32, points: &[Point]) -> Result<Constraint, String> {
+        Ok(Constraint::MoreThan {
+            target,
+            points: point_set(points)?,
+        })
+    }
+
+    pub fn all_same_from(expected: Option<Pips>, points: &[Point]) -> Result<Constraint, String> {
+        Ok(Constraint::AllSame {
+            expected,
+            points: point_set(points)?,
+        })
+    }
+
+    /// An `AllDifferent` over `points` with no excluded pip values.
+    pub fn all_different_from(points: &[Point]) -> Result<Constraint, String> {
+        Ok(Constraint::AllDifferent {
+            excluded: Arc::new(HashSet::new()),
+            points: point_set(points)?,
+        })
+    }
+
     pub fn validate(&self) -> Result<(), String> {
         match self {
             Constraint::AllSame { points, .. } => {
@@ -56,33 +125,24 @@ impl Constraint {
                 }
                 Ok(())
             }
-            Constraint::Exactly { target, points } => {
-                Self::validate_numeric(*target, points, true, "Exactly")
-            }
-            Constraint::LessThan { target, points } => {
+            Constraint::Exactly { target, .. } => self.validate_numeric(*target, true, "Exactly"),
+            Constraint::LessThan { target, .. } => {
                 if *target == 0 {
                     return Err("LessThan target must be positive.".to_string());
                 }
-                if *target > (points.len() as u32) * (Pips::MAX as u32) {
+                if *target > checked_max_sum(self.points().len())? {
                     return Err(
                         "LessThan target must not exceed the maximum achievable sum.".to_string(),
                     );
                 }
                 Ok(())
             }
-            Constraint::MoreThan { target, points } => {
-                Self::validate_numeric(*target, points, true, "MoreThan")
-            }
+            Constraint::MoreThan { target, .. } => self.validate_numeric(*target, true, "MoreThan"),
         }
     }
 
-    fn validate_numeric(
-        target: u32,
-        points: &HashSet<Point>,
-        allow_zero: bool,
-        label: &str,
-    ) -> Result<(), String> {
-        if points.is_empty() {
+    fn validate_numeric(&self, target: u32, allow_zero: bool, label: &str) -> Result<(), String> {
+        if self.points().is_empty() {
             return Err(format!(
                 "{} constraint must reference at least one point.",
                 label
@@ -91,8 +151,7 @@ impl Constraint {
         if !allow_zero && target == 0 {
             return Err(format!("{} target must be positive.", label));
         }
-        let max = (points.len() as u32) * (Pips::MAX as u32);
-        if target > max {
+        if target > checked_max_sum(self.points().len())? {
             return Err(format!(
                 "{} target exceeds achievable sum for the given points.",
                 label
@@ -101,6 +160,30 @@ impl Constraint {
         Ok(())
     }
 
+    /// Largest sum the constraint's cells can add up to: every cell at `Pips::MAX`. Saturates
+    /// at `u32::MAX` for regions too large to validate.
+    pub fn max_achievable_sum(&self) -> u32 {
+        max_sum_for(self.points().len())
+    }
+
+    /// Whether every assignment satisfies the constraint, so it only costs the solver time: a
+    /// `LessThan` above the largest achievable sum, or an open `AllSame` over a single cell.
+    pub fn is_trivial(&self) -> bool {
+        match self {
+            Constraint::LessThan { target, .. } => *target > self.max_achievable_sum(),
+            Constraint::AllSame {
+                expected: None,
+                points,
+            } => points.len() == 1,
+            _ => false,
+        }
+    }
+
+    /// Smallest sum the constraint's cells can add up to: every cell at `Pips::MIN`.
+    pub fn min_achievable_sum(&self) -> u32 {
+        self.points().len() as u32 * Pips::MIN as u32
+    }
+
     pub fn points(&self) -> &HashSet<Point> {
         match self {
             Constraint::AllSame { points, .. }
@@ -111,6 +194,152 @@ impl Constraint {
         }
     }
 
+    /// The same kind of constraint over `points`, which must be a subset of the current
+    /// points. The result is revalidated, so shrinking a failing case never yields a
+    /// constraint the loader would reject.
+    #[cfg(test)]
+    pub(crate) fn with_points(&self, points: HashSet<Point>) -> Result<Constraint, String> {
+        if let Some(point) = points.iter().find(|point| !self.points().contains(point)) {
+            return Err(format!("Point {} is not part of the constraint.", point));
+        }
+        let points = Arc::new(points);
+        let rebuilt = match self {
+            Constraint::AllSame { expected, .. } => Constraint::AllSame {
+                expected: *expected,
+                points,
+            },
+            Constraint::AllDifferent { excluded, .. } => Constraint::AllDifferent {
+                excluded: Arc::clone(excluded),
+                points,
+            },
+            Constraint::Exactly { target, .. } => Constraint::Exactly {
+                target: *target,
+                points,
+            },
+            Constraint::LessThan { target, .. } => Constraint::LessThan {
+                target: *target,
+                points,
+            },
+            Constraint::MoreThan { target, .. } => Constraint::MoreThan {
+                target: *target,
+                points,
+            },
+        };
+        rebuilt.validate()?;
+        Ok(rebuilt)
+    }
+
+    /// Compares what two constraints require of their cells rather than how they are written,
+    /// so `Exactly 0`, `LessThan 1` and `AllSame 0` over the same points are all equal.
+    pub fn semantically_eq(&self, other: &Constraint) -> bool {
+        self.normalized() == other.normalized()
+    }
+
+    fn normalized(&self) -> Constraint {
+        let points = match self {
+            Constraint::AllSame { points, .. }
+            | Constraint::AllDifferent { points, .. }
+            | Constraint::Exactly { points, .. }
+            | Constraint::LessThan { points, .. }
+            | Constraint::MoreThan { points, .. } => points.clone(),
+        };
+        let max = self.max_achievable_sum();
+        let fixed = |value: u32| -> Option<Pips> {
+            if value == 0 {
+                Pips::new(Pips::MIN).ok()
+            } else if value == max {
+                Pips::new(Pips::MAX).ok()
+            } else if points.len() == 1 {
+                Pips::new(value as u8).ok()
+            } else {
+                None
+            }
+        };
+        let target = match self {
+            Constraint::Exactly { target, .. } => Some(*target),
+            Constraint::LessThan { target: 1, .. } => Some(0),
+            Constraint::MoreThan { target, .. } if target + 1 == max => Some(max),
+            _ => None,
+        };
+        if let Some(target) = target {
+            return match fixed(target) {
+                Some(pips) => Constraint::AllSame {
+                    expected: Some(pips),
+                    points,
+                },
+                None => Constraint::Exactly { target, points },
+            };
+        }
+        if let Constraint::AllDifferent { excluded, .. } = self {
+            let allowed: Vec<Pips> = (Pips::MIN..=Pips::MAX)
+                .filter_map(|value| Pips::new(value).ok())
+                .filter(|pips| !excluded.contains(pips))
+                .collect();
+            if points.len() == 1 && allowed.len() == 1 {
+                return Constraint::AllSame {
+                    expected: Some(allowed[0]),
+                    points,
+                };
+            }
+        }
+        self.clone()
+    }
+
+    /// Drops the points that are not on `board`, returning `None` when nothing remains or the
+    /// trimmed constraint would be trivially satisfied or invalid.
+    pub fn restrict_to(&self, board: &Board) -> Option<Constraint> {
+        if self
+            .points()
+            .iter()
+            .all(|point| board.contains_point(point))
+        {
+            return Some(self.clone());
+        }
+        let points: HashSet<Point> = self
+            .points()
+            .iter()
+            .copied()
+            .filter(|point| board.contains_point(point))
+            .collect();
+        if points.is_empty() {
+            return None;
+        }
+        let points = Arc::new(points);
+        let restricted = match self {
+            Constraint::AllSame { expected, .. } => {
+                if expected.is_none() && points.len() == 1 {
+                    return None;
+                }
+                Constraint::AllSame {
+                    expected: *expected,
+                    points,
+                }
+            }
+            Constraint::AllDifferent { excluded, .. } => {
+                if excluded.is_empty() && points.len() == 1 {
+                    return None;
+                }
+                Constraint::AllDifferent {
+                    excluded: excluded.clone(),
+                    points,
+                }
+            }
+            Constraint::Exactly { target, .. } => Constraint::Exactly {
+                target: *target,
+                points,
+            },
+            Constraint::LessThan { target, .. } => Constraint::LessThan {
+                target: *target,
+                points,
+            },
+            Constraint::MoreThan { target, .. } => Constraint::MoreThan {
+                target: *target,
+                points,
+            },
+        };
+        restricted.validate().ok().map(|_| restricted)
+    }
+
     pub fn reduce_assignment(&self, assignment: &Assignment) -> Result<Option<Constraint>, String> {
         if !self.points().contains(&assignment.point) {
             return Ok(Some(self.clone()));
@@ -148,7 +377,7 @@ impl Constraint {
                             Ok(None)
                         } else if size == 2 {
                             Ok(Some(Constraint::Exactly {
-                                target: target.value() as u32,
+                                target: target.value_u32(),
                                 points: remaining,
                             }))
                         } else {
@@ -165,7 +394,7 @@ impl Constraint {
                             let pip = assignment.pips;
                             if size == 2 {
                                 Ok(Some(Constraint::Exactly {
-                                    target: pip.value() as u32,
+                                    target: pip.value_u32(),
                                     points: remaining,
                                 }))
                             } else {
@@ -182,7 +411,7 @@ impl Constraint {
                 let mut remaining = Arc::clone(points);
                 Arc::make_mut(&mut remaining).remove(&assignment.point);
                 let size = points.len();
-                let pip_value = assignment.pips.value() as u32;
+                let pip_value = assignment.pips.value_u32();
                 if size == 1 {
                     if pip_value == *target {
                         Ok(None)
@@ -199,7 +428,7 @@ impl Constraint {
                     ))
                 } else {
                     let remaining_target = target - pip_value;
-                    let max_possible = remaining.len() as u32 * (Pips::MAX as u32);
+                    let max_possible = max_sum_for(remaining.len());
                     if remaining_target > max_possible {
                         Err(format!(
                             "The remaining sum {} is unachievable with {} points.",
@@ -218,7 +447,7 @@ impl Constraint {
                 let mut remaining = Arc::clone(points);
                 Arc::make_mut(&mut remaining).remove(&assignment.point);
                 let size = points.len();
-                let pip_value = assignment.pips.value() as u32;
+                let pip_value = assignment.pips.value_u32();
                 if pip_value >= *target {
                     return Err(format!(
                         "The pips {} is not less than the target sum {}.",
@@ -229,7 +458,9 @@ impl Constraint {
                     Ok(None)
                 } else {
                     let remaining_target = target - pip_value;
-                    if size == 2 && remaining_target == 1 {
+                    // A remaining sum below 1 pins every remaining cell to 0, whatever
+                    // the region size.
+                    if remaining_target == 1 {
                         Ok(Some(Constraint::Exactly {
                             target: 0,
                             points: remaining,
@@ -246,10 +477,10 @@ impl Constraint {
                 let mut remaining = Arc::clone(points);
                 Arc::make_mut(&mut remaining).remove(&assignment.point);
                 let size = points.len();
-                let pip_value = assignment.pips.value() as i32;
+                let pip_value = i64::from(assignment.pips.value_u32());
                 let remaining_points = remaining.len();
                 if size == 1 {
-                    if pip_value > *target as i32 {
+                    if pip_value > *target as i64 {
                         Ok(None)
                     } else {
                         Err(format!(
@@ -259,33 +490,93 @@ impl Constraint {
                         ))
                     }
                 } else {
-                    let remaining_target = *target as i32 - pip_value;
-                    if remaining_points == 1 && remaining_target == 5 {
+                    let remaining_target = *target as i64 - pip_value;
+                    let max_possible = max_sum_for(remaining_points) as i64;
+                    if let Some(forced) =
+                        forced_more_than(remaining_target, remaining_points, Pips::MAX)
+                    {
                         Ok(Some(Constraint::Exactly {
-                            target: 6,
+                            target: forced,
                             points: remaining,
                         }))
                     } else if remaining_target < 0 {
                         Ok(None)
+                    } else if remaining_target >= max_possible {
+                        Err(format!(
+                            "The remaining sum {} is unachievable with {} points.",
+                            remaining_target, remaining_points
+                        ))
                     } else {
-                        let max_possible = (remaining_points as i32) * (Pips::MAX as i32);
-                        if remaining_target >= max_possible {
-                            Err(format!(
-                                "The remaining sum {} is unachievable with {} points.",
-                                remaining_target, remaining_points
-                            ))
-                        } else {
-                            Ok(Some(Constraint::MoreThan {
-                                target: remaining_target as u32,
-                                points: remaining,
-                            }))
-                        }
+                        Ok(Some(Constraint::MoreThan {
+                            target: remaining_target as u32,
+                            points: remaining,
+                        }))
                     }
                 }
             }
         }
     }
 
+    /// The single pip value this constraint pins its cells to, if any: the value of an
+    /// `AllSame Some(v)`, or the target of a one-cell `Exactly` (what an `AllSame` region
+    /// collapses to once all but one of its cells are filled).
+    pub fn resolved_value(&self) -> Option<Pips> {
+        match self {
+            Constraint::AllSame { expected, .. } => *expected,
+            Constraint::Exactly { target, points } if points.len() == 1 => u8::try_from(*target)
+                .ok()
+                .and_then(|value| Pips::new(value).ok()),
+            _ => None,
+        }
+    }
+
+    /// Ground truth for the constraint's plain meaning, checked against a full assignment of
+    /// its points. Missing points count as unsatisfied.
+    pub fn is_satisfied_by(&self, assignment: &HashMap<Point, Pips>) -> bool {
+        let Some(values) = self
+            .points()
+            .iter()
+            .map(|point| assignment.get(point).copied())
+            .collect::<Option<Vec<Pips>>>()
+        else {
+            return false;
+        };
+        let sum: u32 = values.iter().map(|pips| pips.value_u32()).sum();
+        match self {
+            Constraint::AllSame { expected, .. } => {
+                let first = expected.or_else(|| values.first().copied());
+                values.iter().all(|pips| Some(*pips) == first)
+            }
+            Constraint::AllDifferent { excluded, .. } => {
+                let distinct: HashSet<Pips> = values.iter().copied().collect();
+                distinct.len() == values.len() && values.iter().all(|pips| !excluded.contains(pips))
+            }
+            Constraint::Exactly { target, .. } => sum == *target,
+            Constraint::LessThan { target, .. } => sum < *target,
+            Constraint::MoreThan { target, .. } => sum > *target,
+        }
+    }
+
+    /// How far a fully assigned sum bound sits from its limit: `target - sum` for `LessThan`,
+    /// `sum - target` for `MoreThan`, so a satisfied bound has slack of at least 1. `None` for
+    /// other constraints or when a point is unassigned.
+    pub fn slack(&self, assignment: &HashMap<Point, Pips>) -> Option<i64> {
+        let sum = self
+            .points()
+            .iter()
+            .map(|point| {
+                assignment
+                    .get(point)
+                    .map(|pips| i64::from(pips.value_u32()))
+            })
+            .sum::<Option<i64>>()?;
+        match self {
+            Constraint::LessThan { target, .. } => Some(*target as i64 - sum),
+            Constraint::MoreThan { target, .. } => Some(sum - *target as i64),
+            _ => None,
+        }
+    }
+
     pub fn reduce_placement(&self, placement: &Placement) -> Result<Option<Constraint>, String> {
         let assignments = placement.assignments();
         assignments
@@ -339,9 +630,13 @@ impl fmt::Display for Constraint {
 
 #[cfg(test)]
 mod tests {
-    use super::{Constraint, reduce_constraints};
-    use crate::model::{piece::Piece, pips::Pips, placement::Placement, point::Point};
-    use std::collections::HashSet;
+    use super::{Constraint, checked_max_sum, forced_more_than, reduce_constraints};
+    use crate::model::{
+        assignment::Assignment, board::Board, piece::Piece, pips::Pips, placement::Placement,
+        point::Point,
+    };
+    use crate::util::rng::SimpleRng;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
 
     fn domino(a: u8, b: u8) -> Piece {
@@ -352,6 +647,98 @@ mod tests {
         Arc::new(points.iter().copied().collect())
     }
 
+    #[test]
+    fn semantically_eq_normalizes_equivalent_forms() {
+        let pair = set_of(&[Point::new(0, 0), Point::new(1, 0)]);
+        let zero = Constraint::Exactly {
+            target: 0,
+            points: pair.clone(),
+        };
+        let below_one = Constraint::LessThan {
+            target: 1,
+            points: pair.clone(),
+        };
+        let all_blank = Constraint::AllSame {
+            expected: Some(Pips::new(0).unwrap()),
+            points: pair.clone(),
+        };
+        assert_ne!(zero, below_one);
+        assert!(zero.semantically_eq(&below_one));
+        assert!(zero.semantically_eq(&all_blank));
+
+        let single = set_of(&[Point::new(2, 2)]);
+        let exactly_four = Constraint::Exactly {
+            target: 4,
+            points: single.clone(),
+        };
+        let only_four = Constraint::AllDifferent {
+            excluded: Arc::new(
+                [0, 1, 2, 3, 5, 6]
+                    .into_iter()
+                    .map(|value| Pips::new(value).unwrap())
+                    .collect(),
+            ),
+            points: single.clone(),
+        };
+        assert!(exactly_four.semantically_eq(&only_four));
+
+        let twelve = Constraint::MoreThan {
+            target: 11,
+            points: pair.clone(),
+        };
+        let all_six = Constraint::AllSame {
+            expected: Some(Pips::new(6).unwrap()),
+            points: pair.clone(),
+        };
+        assert!(twelve.semantically_eq(&all_six));
+
+        let different_points = Constraint::Exactly {
+            target: 4,
+            points: set_of(&[Point::new(0, 0)]),
+        };
+        assert!(!exactly_four.semantically_eq(&different_points));
+        assert!(!zero.semantically_eq(&Constraint::Exactly {
+            target: 1,
+            points: pair,
+        }));
+    }
+
+    #[test]
+    fn restrict_to_keeps_only_on_board_points() {
+        let region = Constraint::Exactly {
+            target: 5,
+            points: set_of(&[Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)]),
+        };
+        let board = Board::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect());
+        let restricted = region.restrict_to(&board).expect("still satisfiable");
+        assert_eq!(
+            restricted,
+            Constraint::Exactly {
+                target: 5,
+                points: set_of(&[Point::new(0, 0), Point::new(1, 0)]),
+            }
+        );
+
+        let elsewhere = Board::new([Point::new(4, 4)].into_iter().collect());
+        assert_eq!(region.restrict_to(&elsewhere), None);
+    }
+
+    #[test]
+    fn restrict_to_drops_constraints_that_become_invalid() {
+        let region = Constraint::Exactly {
+            target: 13,
+            points: set_of(&[Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)]),
+        };
+        let board = Board::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect());
+        assert_eq!(region.restrict_to(&board), None);
+
+        let all_same = Constraint::AllSame {
+            expected: None,
+            points: set_of(&[Point::new(0, 0), Point::new(0, 1)]),
+        };
+        assert_eq!(all_same.restrict_to(&board), None);
+    }
+
     #[test]
     fn all_same_mismatch_fails() {
         let constraint = Constraint::AllSame {
@@ -377,4 +764,288 @@ mod tests {
         let reduced = reduce_constraints(&[constraint], &placement).unwrap();
         assert!(reduced.is_empty());
     }
+
+    fn random_constraint(rng: &mut SimpleRng) -> Constraint {
+        let size = 1 + rng.gen_below(4) as u32;
+        let points = Arc::new((0..size).map(|x| Point::new(x, 0)).collect::<HashSet<_>>());
+        let max = size * Pips::MAX as u32;
+        let pip = |rng: &mut SimpleRng| Pips::new(rng.gen_below(7) as u8).unwrap();
+        match rng.gen_below(5) {
+            0 => Constraint::AllSame {
+                expected: if rng.gen_below(2) == 0 {
+                    None
+                } else {
+                    Some(pip(rng))
+                },
+                points,
+            },
+            1 => {
+                let excluded = (0..rng.gen_below(3)).map(|_| pip(rng)).collect();
+                Constraint::AllDifferent {
+                    excluded: Arc::new(excluded),
+                    points,
+                }
+            }
+            2 => Constraint::Exactly {
+                target: rng.gen_below(max as usize + 1) as u32,
+                points,
+            },
+            3 => Constraint::LessThan {
+                target: 1 + rng.gen_below(max as usize) as u32,
+                points,
+            },
+            _ => Constraint::MoreThan {
+                target: rng.gen_below(max as usize + 1) as u32,
+                points,
+            },
+        }
+    }
+
+    /// Feeds the assignment point by point; `true` means the constraint reduced to `None`.
+    fn reduces_to_none(constraint: &Constraint, order: &[(Point, Pips)]) -> bool {
+        let mut current = Some(constraint.clone());
+        for &(point, pips) in order {
+            let Some(active) = current else {
+                return true;
+            };
+            match active.reduce_assignment(&Assignment::new(pips, point)) {
+                Ok(next) => current = next,
+                Err(_) => return false,
+            }
+        }
+        current.is_none()
+    }
+
+    #[test]
+    fn reduction_agrees_with_ground_truth_on_random_cases() {
+        let mut rng = SimpleRng::new(Some(2395), 0, 0);
+        let mut checked = 0;
+        while checked < 5000 {
+            let constraint = random_constraint(&mut rng);
+            if constraint.validate().is_err() {
+                continue;
+            }
+            let mut order: Vec<(Point, Pips)> = constraint
+                .points()
+                .iter()
+                .map(|&point| (point, Pips::new(rng.gen_below(7) as u8).unwrap()))
+                .collect();
+            order.sort_by_key(|(point, _)| point.x);
+            let rotate_by = rng.gen_below(order.len());
+            order.rotate_left(rotate_by);
+
+            let assignment: HashMap<Point, Pips> = order.iter().copied().collect();
+            assert_eq!(
+                reduces_to_none(&constraint, &order),
+                constraint.is_satisfied_by(&assignment),
+                "{} with {:?}",
+                constraint,
+                order
+            );
+            checked += 1;
+        }
+    }
+
+    #[test]
+    fn is_satisfied_by_requires_every_point() {
+        let constraint = Constraint::MoreThan {
+            target: 1,
+            points: set_of(&[Point::new(0, 0), Point::new(1, 0)]),
+        };
+        let mut assignment = HashMap::new();
+        assignment.insert(Point::new(0, 0), Pips::new(6).unwrap());
+        assert!(!constraint.is_satisfied_by(&assignment));
+        assignment.insert(Point::new(1, 0), Pips::new(0).unwrap());
+        assert!(constraint.is_satisfied_by(&assignment));
+    }
+
+    #[test]
+    fn resolved_value_reports_pinned_pips() {
+        let pair = set_of(&[Point::new(0, 0), Point::new(1, 0)]);
+        let pinned = Constraint::AllSame {
+            expected: Some(Pips::new(3).unwrap()),
+            points: Arc::clone(&pair),
+        };
+        assert_eq!(pinned.resolved_value(), Some(Pips::new(3).unwrap()));
+
+        let open = Constraint::AllSame {
+            expected: None,
+            points: Arc::clone(&pair),
+        };
+        assert_eq!(open.resolved_value(), None);
+
+        // Filling one cell of a two-cell AllSame leaves a one-cell Exactly.
+        let reduced = open
+            .reduce_assignment(&Assignment::new(Pips::new(4).unwrap(), Point::new(0, 0)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(reduced.resolved_value(), Some(Pips::new(4).unwrap()));
+
+        let sum = Constraint::Exactly {
+            target: 4,
+            points: pair,
+        };
+        assert_eq!(sum.resolved_value(), None);
+    }
+
+    #[test]
+    fn achievable_sums_span_min_to_max_pips() {
+        let constraint = Constraint::Exactly {
+            target: 9,
+            points: Arc::new((0..3).map(|x| Point::new(x, 0)).collect()),
+        };
+        assert_eq!(constraint.max_achievable_sum(), 18);
+        assert_eq!(constraint.min_achievable_sum(), 0);
+    }
+
+    #[test]
+    fn oversized_regions_fail_validation_instead_of_wrapping() {
+        // A point set this large cannot be allocated in a test, so the region size is fed to
+        // the helper that `validate` uses directly.
+        let too_many = u32::MAX as usize / Pips::MAX as usize + 1;
+        assert_eq!(checked_max_sum(3), Ok(18));
+        assert_eq!(
+            checked_max_sum(too_many),
+            Err("Region too large for sum constraint.".to_string())
+        );
+        assert!(checked_max_sum(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn with_points_rebuilds_over_a_smaller_region() {
+        let row: Vec<Point> = (0..4).map(|x| Point::new(x, 0)).collect();
+        let wide = Constraint::Exactly {
+            target: 10,
+            points: set_of(&row),
+        };
+
+        let narrow = wide
+            .with_points(row[..2].iter().copied().collect())
+            .expect("two cells can still reach 10");
+        assert_eq!(
+            narrow,
+            Constraint::Exactly {
+                target: 10,
+                points: set_of(&row[..2]),
+            }
+        );
+        assert!(narrow.validate().is_ok());
+
+        assert!(wide.with_points(HashSet::from([row[0]])).is_err());
+        assert!(wide.with_points(HashSet::from([Point::new(9, 9)])).is_err());
+    }
+
+    #[test]
+    fn more_than_one_below_the_top_pip_forces_exactly_max() {
+        let constraint = Constraint::MoreThan {
+            target: Pips::MAX as u32 + 2,
+            points: set_of(&[Point::new(0, 0), Point::new(1, 0)]),
+        };
+        let reduced = constraint
+            .reduce_assignment(&Assignment::new(Pips::new(3).unwrap(), Point::new(0, 0)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reduced,
+            Constraint::Exactly {
+                target: Pips::MAX as u32,
+                points: set_of(&[Point::new(1, 0)]),
+            }
+        );
+
+        assert_eq!(forced_more_than(5, 1, 6), Some(6));
+        assert_eq!(forced_more_than(8, 1, 9), Some(9));
+        assert_eq!(forced_more_than(5, 1, 9), None);
+        assert_eq!(forced_more_than(8, 2, 9), None);
+    }
+
+    #[test]
+    fn is_trivial_flags_unreachable_less_than() {
+        let pair = set_of(&[Point::new(0, 0), Point::new(1, 0)]);
+        let loose = Constraint::LessThan {
+            target: 13,
+            points: Arc::clone(&pair),
+        };
+        assert!(loose.is_trivial());
+
+        let tight = Constraint::LessThan {
+            target: 12,
+            points: Arc::clone(&pair),
+        };
+        assert!(!tight.is_trivial());
+        assert!(
+            !Constraint::Exactly {
+                target: 0,
+                points: pair,
+            }
+            .is_trivial()
+        );
+    }
+
+    #[test]
+    fn exactly_from_matches_hash_set_construction() {
+        let points = [Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)];
+        let from_slice = Constraint::exactly_from(7, &points).unwrap();
+        let from_set = Constraint::Exactly {
+            target: 7,
+            points: Arc::new(points.iter().copied().collect()),
+        };
+        assert_eq!(from_slice, from_set);
+
+        let repeated = [Point::new(0, 0), Point::new(0, 0)];
+        assert!(Constraint::exactly_from(3, &repeated).is_err());
+        assert!(Constraint::all_different_from(&repeated).is_err());
+    }
+
+    #[test]
+    fn less_than_reductions_match_ground_truth() {
+        for size in 2..=4u32 {
+            let points: Vec<Point> = (0..size).map(|x| Point::new(x, 0)).collect();
+            for target in 1..=size * Pips::MAX as u32 + 1 {
+                let constraint = Constraint::LessThan {
+                    target,
+                    points: Arc::new(points.iter().copied().collect()),
+                };
+                for code in 0..7u32.pow(size) {
+                    let values: Vec<u32> = (0..size).map(|i| code / 7u32.pow(i) % 7).collect();
+                    let assignment: HashMap<Point, Pips> = points
+                        .iter()
+                        .zip(&values)
+                        .map(|(point, value)| (*point, Pips::new(*value as u8).unwrap()))
+                        .collect();
+
+                    let mut current = Some(constraint.clone());
+                    let mut placed = 0;
+                    let mut pruned = false;
+                    for (point, value) in points.iter().zip(&values) {
+                        let Some(open) = current.take() else { break };
+                        let step = Assignment::new(Pips::new(*value as u8).unwrap(), *point);
+                        placed += value;
+                        match open.reduce_assignment(&step) {
+                            Ok(next) => {
+                                // Zeros in every open cell are the cheapest completion.
+                                assert!(placed < target, "{} kept {:?}", constraint, values);
+                                if let Some(Constraint::LessThan { target: left, .. }) = &next {
+                                    assert!(*left > 1, "{} left LessThan 1", constraint);
+                                }
+                                current = next;
+                            }
+                            Err(_) => {
+                                assert!(placed >= target, "{} pruned {:?}", constraint, values);
+                                pruned = true;
+                                break;
+                            }
+                        }
+                    }
+                    assert_eq!(
+                        !pruned && current.is_none(),
+                        constraint.is_satisfied_by(&assignment),
+                        "{} with {:?}",
+                        constraint,
+                        values
+                    );
+                }
+            }
+        }
+    }
 }
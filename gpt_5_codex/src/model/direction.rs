@@ -1,3 +1,4 @@
+use super::piece::PolyShape;
 use std::fmt;
 
 /// Compass direction used when placing a domino.
@@ -10,13 +11,45 @@ pub enum Direction {
 }
 
 impl Direction {
-    #[allow(dead_code)]
     pub const ALL: [Direction; 4] = [
         Direction::North,
         Direction::East,
         Direction::South,
         Direction::West,
     ];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// One step this way on the board, where `y` grows southwards.
+    pub(crate) fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    /// Domino `orientation_index` whose cells run along this direction's axis. Domino
+    /// orientations are deduplicated by footprint, so opposite directions share an index and
+    /// differ only in pip order; see `Piece::direction_for_orientation`.
+    pub fn to_domino_orientation_index(self) -> usize {
+        PolyShape::Domino
+            .orientations()
+            .iter()
+            .position(|cells| {
+                let step = (cells[1].0 - cells[0].0, cells[1].1 - cells[0].1);
+                step == self.delta() || step == self.opposite().delta()
+            })
+            .expect("domino orientations cover both axes")
+    }
 }
 
 impl fmt::Display for Direction {
@@ -34,9 +67,48 @@ impl fmt::Display for Direction {
 #[cfg(test)]
 mod tests {
     use super::Direction;
+    use crate::model::{Piece, Pips, Placement, Point, PolyShape};
 
     #[test]
     fn all_directions_listed() {
         assert_eq!(Direction::ALL.len(), 4);
     }
+
+    #[test]
+    fn domino_orientation_index_round_trips_every_direction() {
+        let piece = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        for direction in Direction::ALL {
+            let index = direction.to_domino_orientation_index();
+            let along = Piece::direction_for_orientation(PolyShape::Domino, index)
+                .expect("domino orientation");
+            assert_eq!(along.to_domino_orientation_index(), index);
+
+            // Heading against the orientation's own direction means laying the pips reversed.
+            let mut pips = piece.pips().to_vec();
+            if along == direction.opposite() {
+                pips.reverse();
+            } else {
+                assert_eq!(along, direction);
+            }
+            let placement = Placement::new(piece.clone(), Point::new(1, 1), index, pips);
+            let cells = placement.assignments();
+            let first = cells.iter().find(|cell| cell.pips.value() == 1).unwrap();
+            let second = cells.iter().find(|cell| cell.pips.value() == 2).unwrap();
+            let step = (
+                second.point.x as i32 - first.point.x as i32,
+                second.point.y as i32 - first.point.y as i32,
+            );
+            assert_eq!(step, direction.delta(), "{}", direction);
+        }
+    }
+
+    #[test]
+    fn only_dominoes_have_directions() {
+        assert_eq!(Piece::direction_for_orientation(PolyShape::TriI, 0), None);
+        let count = PolyShape::Domino.orientations().len();
+        assert_eq!(
+            Piece::direction_for_orientation(PolyShape::Domino, count),
+            None
+        );
+    }
 }
@@ -1,12 +1,24 @@
 use super::{
-    board::{Board, EMPTY_BOARD},
-    constraint::ConstraintSet,
-    piece::Piece,
+    board::{Board, EMPTY_BOARD, orthogonal_neighbors},
+    constraint::{ConstraintSet, reduce_constraints},
+    piece::{Piece, PolyShape, remove_one},
+    placement::Placement,
     point::Point,
 };
 use once_cell::sync::Lazy;
 use std::cmp::Ordering;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+/// Search nodes `Game::solvable` explores before giving up.
+pub const SOLVABLE_NODE_CAP: usize = 1_000_000;
+
+/// Extra checks for `Game::validate_with`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ValidateOptions {
+    /// Reject games that fail `Game::is_tileable_lower_bound`. Off by default, since it walks
+    /// every placement of every piece shape.
+    pub check_tileable: bool,
+}
 
 /// Represents a full game state, including remaining board points, pieces, and constraints.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -26,14 +38,31 @@ impl Game {
     }
 
     pub fn validate(&self) -> Result<(), String> {
+        self.validate_with(&ValidateOptions::default())
+    }
+
+    /// `validate`, plus whichever extra checks `options` turns on.
+    pub fn validate_with(&self, options: &ValidateOptions) -> Result<(), String> {
         let total_cells: usize = self
             .pieces
             .iter()
             .map(|piece| piece.shape().cell_count())
             .sum();
-        if self.board.len() != total_cells {
+        let board_cells = self.board.len();
+        if board_cells != total_cells {
+            let delta = if board_cells > total_cells {
+                format!("need {} more cells", board_cells - total_cells)
+            } else {
+                format!("{} cells too many", total_cells - board_cells)
+            };
+            return Err(format!(
+                "Board has {} cells but pieces total {} ({}).",
+                board_cells, total_cells, delta
+            ));
+        }
+        if options.check_tileable && !self.is_tileable_lower_bound() {
             return Err(
-                "Board must have the same number of points as the total cells across pieces."
+                "No tiling exists: a board cell or a piece has no placement on the board."
                     .to_string(),
             );
         }
@@ -59,6 +88,33 @@ impl Game {
         Ok(())
     }
 
+    /// Plays each placement in turn, returning the state left afterwards: covered cells
+    /// removed from the board, used pieces removed, and constraints reduced. A complete,
+    /// valid solution yields a game for which `is_won` holds.
+    pub fn apply_placements(&self, placements: &[Placement]) -> Result<Game, String> {
+        placements
+            .iter()
+            .try_fold(self.clone(), |state, placement| {
+                state.apply_placement(placement)
+            })
+    }
+
+    fn apply_placement(&self, placement: &Placement) -> Result<Game, String> {
+        let board = self
+            .board
+            .remove_points(&placement.points())
+            .map_err(|err| {
+                format!(
+                    "Placement {} does not fit the remaining board: {}",
+                    placement, err
+                )
+            })?;
+        let pieces = remove_one(self.pieces.clone(), &placement.piece)?;
+        let constraints = reduce_constraints(&self.constraints, placement)
+            .map_err(|_| format!("Placement {} violates a constraint.", placement))?;
+        Ok(Game::new(board, pieces, constraints))
+    }
+
     pub fn is_won(&self) -> bool {
         self.board.is_empty() && self.pieces.is_empty() && self.constraints.is_empty()
     }
@@ -104,6 +160,116 @@ impl Game {
             .map(|(point, _, _)| point)
     }
 
+    /// Partitions the board into its connected components, in the order `pivot_point` visits
+    /// them, pairing each with the constraints on its cells. Errors if a constraint straddles
+    /// components.
+    pub fn partition_components(&self) -> Result<Vec<(Board, ConstraintSet)>, String> {
+        let components = connected_components(&self.board);
+
+        let mut constraints: Vec<ConstraintSet> = vec![Vec::new(); components.len()];
+        for constraint in &self.constraints {
+            let owners: HashSet<usize> = constraint
+                .points()
+                .iter()
+                .filter_map(|point| {
+                    components
+                        .iter()
+                        .position(|component| component.point_set.contains(point))
+                })
+                .collect();
+            match owners.len() {
+                0 => {}
+                1 => {
+                    let owner = owners.into_iter().next().unwrap();
+                    constraints[owner].push(constraint.clone());
+                }
+                _ => {
+                    return Err(format!(
+                        "Constraint {} straddles {} board components.",
+                        constraint,
+                        owners.len()
+                    ));
+                }
+            }
+        }
+
+        Ok(components
+            .into_iter()
+            .zip(constraints)
+            .map(|(component, constraints)| (Board::new(component.point_set), constraints))
+            .collect())
+    }
+
+    /// Splits the game into one sub-game per connected board component, in
+    /// `partition_components` order, each holding exactly the pieces that must cover it. Pieces
+    /// carry no position, so a piece goes to a component only when it is forced there: its
+    /// shape fits no other component, or every other component it fits is already full. Errors
+    /// when the pieces cannot be divided that way, or when a part fails `validate`.
+    pub fn split_components(&self) -> Result<Vec<Game>, String> {
+        let parts = self.partition_components()?;
+
+        let mut deficits: Vec<usize> = parts.iter().map(|(board, _)| board.len()).collect();
+        let mut fits: HashMap<PolyShape, Vec<usize>> = HashMap::new();
+        let mut pending: Vec<(&Piece, Vec<usize>)> = Vec::with_capacity(self.pieces.len());
+        for piece in &self.pieces {
+            let owners = fits.entry(piece.shape()).or_insert_with(|| {
+                (0..parts.len())
+                    .filter(|&index| shape_fits(&parts[index].0, piece.shape()))
+                    .collect()
+            });
+            pending.push((piece, owners.clone()));
+        }
+
+        let mut pieces: Vec<Vec<Piece>> = vec![Vec::new(); parts.len()];
+        while !pending.is_empty() {
+            let before = pending.len();
+            let mut index = 0;
+            while index < pending.len() {
+                let size = pending[index].0.shape().cell_count();
+                pending[index].1.retain(|&owner| deficits[owner] >= size);
+                match pending[index].1.as_slice() {
+                    [] => {
+                        return Err(format!(
+                            "Piece {} fits in no board component with room for it.",
+                            pending[index].0
+                        ));
+                    }
+                    &[owner] => {
+                        let (piece, _) = pending.swap_remove(index);
+                        deficits[owner] -= size;
+                        pieces[owner].push(piece.clone());
+                    }
+                    _ => index += 1,
+                }
+            }
+            if pending.len() == before {
+                return Err(format!(
+                    "{} pieces could go to more than one board component.",
+                    pending.len()
+                ));
+            }
+        }
+
+        let games: Vec<Game> = parts
+            .into_iter()
+            .zip(pieces)
+            .map(|((board, constraints), pieces)| Game::new(board, pieces, constraints))
+            .collect();
+        for game in &games {
+            game.validate()?;
+        }
+        Ok(games)
+    }
+
+    /// How many pieces of each shape the game holds.
+    pub fn piece_shape_histogram(&self) -> HashMap<PolyShape, usize> {
+        let mut histogram = HashMap::new();
+        for piece in &self.pieces {
+            *histogram.entry(piece.shape()).or_insert(0) += 1;
+        }
+        histogram
+    }
+
     pub fn unique_pieces(&self) -> Vec<Piece> {
         let mut unique = HashSet::new();
         let mut list = Vec::new();
@@ -114,6 +280,76 @@ impl Game {
         }
         list
     }
+
+    /// Necessary (not sufficient) condition for a tiling: every board cell is covered by at
+    /// least one on-board placement of some piece, and every piece fits somewhere on the board.
+    /// Cheap enough to run before a full search.
+    pub fn is_tileable_lower_bound(&self) -> bool {
+        let shapes: HashSet<_> = self.pieces.iter().map(|piece| piece.shape()).collect();
+        let mut covered: HashSet<usize> = HashSet::new();
+        for shape in shapes {
+            let mut placeable = false;
+            for offsets in shape.orientations() {
+                for anchor in self.board.iter() {
+                    if let Some(indices) = self.board.placement_indices(anchor, offsets) {
+                        placeable = true;
+                        covered.extend(indices);
+                    }
+                }
+            }
+            if !placeable {
+                return false;
+            }
+        }
+        covered.len() == self.board.len()
+    }
+
+    /// Equality that ignores the order of `pieces` and `constraints`: same board, same pieces
+    /// with the same multiplicities, same constraints.
+    pub fn structurally_eq(&self, other: &Game) -> bool {
+        self.board == other.board
+            && same_multiset(&self.pieces, &other.pieces)
+            && same_multiset(&self.constraints, &other.constraints)
+    }
+
+    /// Whether the game is valid and has a solution. The search stops after
+    /// `SOLVABLE_NODE_CAP` nodes and answers `false` if it got that far without one, so a
+    /// pathological game may be reported unsolvable when it is not.
+    pub fn solvable(&self) -> bool {
+        self.validate().is_ok()
+            && matches!(
+                crate::solver::solve_with_node_limit(self, SOLVABLE_NODE_CAP),
+                Ok(Some(_))
+            )
+    }
+
+    /// NYT-style "easy", "medium" or "hard", from the `solver::estimate_difficulty` node count;
+    /// see `solver::difficulty_label`.
+    pub fn difficulty_label(&self) -> Result<&'static str, String> {
+        crate::solver::estimate_difficulty(self).map(crate::solver::difficulty_label)
+    }
+
+    /// The puzzle's crux: the cell the fewest candidate placements cover before anything is
+    /// placed, with that count. Ties go to the first cell in row-major order.
+    pub fn hardest_cell(&self) -> Option<(Point, usize)> {
+        crate::solver::candidate_counts(self)
+            .into_iter()
+            .min_by_key(|&(point, count)| (count, point.y, point.x))
+    }
+
+    /// Fraction of board cells covered by at least one constraint; 0.0 for an empty board.
+    pub fn constraint_density(&self) -> f64 {
+        if self.board.is_empty() {
+            return 0.0;
+        }
+        let constrained: HashSet<Point> = self
+            .constraints
+            .iter()
+            .flat_map(|constraint| constraint.points().iter().copied())
+            .filter(|point| self.board.contains_point(point))
+            .collect();
+        constrained.len() as f64 / self.board.len() as f64
+    }
 }
 
 #[allow(dead_code)]
@@ -123,11 +359,39 @@ pub static WON_GAME: Lazy<Game> = Lazy::new(|| Game {
     constraints: Vec::new(),
 });
 
+fn same_multiset<T: PartialEq>(left: &[T], right: &[T]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    let mut matched = vec![false; right.len()];
+    left.iter().all(|item| {
+        let found = right
+            .iter()
+            .enumerate()
+            .position(|(index, candidate)| !matched[index] && candidate == item);
+        found.map(|index| matched[index] = true).is_some()
+    })
+}
+
+/// Whether some orientation of `shape` lies entirely on `board`.
+fn shape_fits(board: &Board, shape: PolyShape) -> bool {
+    shape.orientations().iter().any(|offsets| {
+        board
+            .iter()
+            .any(|anchor| board.placement_indices(anchor, offsets).is_some())
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Game;
+    use super::{Game, ValidateOptions};
     use crate::model::{
-        board::Board, constraint::Constraint, piece::Piece, pips::Pips, point::Point,
+        board::Board,
+        constraint::Constraint,
+        piece::{Piece, PolyShape},
+        pips::Pips,
+        placement::Placement,
+        point::Point,
     };
     use std::collections::HashSet;
     use std::sync::Arc;
@@ -146,6 +410,64 @@ mod tests {
         assert!(game.validate().is_err());
     }
 
+    #[test]
+    fn piece_shape_histogram_counts_each_shape() {
+        let pip = |value| Pips::new(value).unwrap();
+        let pieces = vec![
+            Piece::domino(pip(1), pip(2)),
+            Piece::new(PolyShape::TetO, vec![pip(1); 4]).unwrap(),
+            Piece::domino(pip(3), pip(3)),
+            Piece::new(PolyShape::TriL, vec![pip(0); 3]).unwrap(),
+        ];
+        let game = Game::new(Board::default(), pieces, vec![]);
+        let histogram = game.piece_shape_histogram();
+        assert_eq!(histogram.len(), 3);
+        assert_eq!(histogram[&PolyShape::Domino], 2);
+        assert_eq!(histogram[&PolyShape::TetO], 1);
+        assert_eq!(histogram[&PolyShape::TriL], 1);
+    }
+
+    #[test]
+    fn solvable_answers_for_fixtures() {
+        let fixture = |path: &str| {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(path);
+            crate::loader::load_game_from_path(path).expect("load fixture")
+        };
+        assert!(fixture("poly_games/2x5.txt").solvable());
+        assert!(!fixture("poly_games/constraints/domino_impossible.txt").solvable());
+    }
+
+    #[test]
+    fn difficulty_label_rates_an_nyt_easy_puzzle_easy() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../examples/game-2025-08-18-easy.txt");
+        let game = crate::loader::load_game_from_path(path).expect("load fixture");
+        assert_eq!(game.difficulty_label(), Ok("easy"));
+    }
+
+    #[test]
+    fn validation_reports_area_mismatch_numerically() {
+        let points: HashSet<Point> = (0..3)
+            .flat_map(|y| (0..2).map(move |x| Point::new(x, y)))
+            .collect();
+        let piece = || Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+
+        let short = Game::new(Board::new(points.clone()), vec![piece(), piece()], vec![]);
+        let message = short.validate().unwrap_err();
+        assert_eq!(
+            message,
+            "Board has 6 cells but pieces total 4 (need 2 more cells)."
+        );
+
+        let extra = Game::new(Board::new(points), vec![piece(); 4], vec![]);
+        let message = extra.validate().unwrap_err();
+        assert!(message.contains("6 cells"), "{}", message);
+        assert!(message.contains("total 8"), "{}", message);
+        assert!(message.contains("2 cells too many"), "{}", message);
+    }
+
     #[test]
     fn validation_fails_when_constraint_points_overlap() {
         let mut board_points = HashSet::new();
@@ -193,6 +515,250 @@ mod tests {
         let game = Game::new(board, vec![piece], constraints);
         assert!(game.validate().is_err());
     }
+
+    fn square_at(x: u32, y: u32) -> Vec<Point> {
+        vec![
+            Point::new(x, y),
+            Point::new(x + 1, y),
+            Point::new(x, y + 1),
+            Point::new(x + 1, y + 1),
+        ]
+    }
+
+    fn domino(a: u8, b: u8) -> Piece {
+        Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap())
+    }
+
+    fn domino_placement(a: u8, b: u8, x: u32, y: u32, orientation_index: usize) -> Placement {
+        let piece = domino(a, b);
+        let pips = piece.pips().to_vec();
+        Placement::new(piece, Point::new(x, y), orientation_index, pips)
+    }
+
+    #[test]
+    fn apply_placements_replays_a_solution() {
+        let board = Board::new(square_at(0, 0).into_iter().collect());
+        let constraints = vec![Constraint::Exactly {
+            target: 3,
+            points: Arc::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect()),
+        }];
+        let game = Game::new(board, vec![domino(1, 2), domino(4, 4)], constraints);
+
+        let solution = [
+            domino_placement(1, 2, 0, 0, 0),
+            domino_placement(4, 4, 0, 1, 0),
+        ];
+        let finished = game.apply_placements(&solution).expect("solution applies");
+        assert!(finished.is_won());
+
+        let partial = game
+            .apply_placements(&solution[..1])
+            .expect("first move applies");
+        assert_eq!(partial.board.len(), 2);
+        assert_eq!(partial.pieces, vec![domino(4, 4)]);
+        assert!(partial.constraints.is_empty());
+    }
+
+    #[test]
+    fn apply_placements_rejects_invalid_moves() {
+        let board = Board::new(square_at(0, 0).into_iter().collect());
+        let constraints = vec![Constraint::Exactly {
+            target: 8,
+            points: Arc::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect()),
+        }];
+        let game = Game::new(board, vec![domino(1, 2), domino(4, 4)], constraints);
+
+        assert!(
+            game.apply_placements(&[domino_placement(1, 2, 0, 0, 0)])
+                .is_err()
+        );
+        assert!(
+            game.apply_placements(&[domino_placement(4, 4, 1, 1, 0)])
+                .is_err()
+        );
+        assert!(
+            game.apply_placements(&[domino_placement(6, 6, 0, 1, 0)])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn lower_bound_rejects_unreachable_corner() {
+        // A 2x2 square plus a corner cell touching it only diagonally.
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.insert(Point::new(2, 2));
+        let game = Game::new(Board::new(board_points), vec![domino(1, 1)], vec![]);
+        assert!(!game.is_tileable_lower_bound());
+    }
+
+    #[test]
+    fn lower_bound_rejects_piece_too_large_for_board() {
+        let board = Board::new(square_at(0, 0).into_iter().collect());
+        let long = Piece::new(PolyShape::TriI, vec![Pips::new(0).unwrap(); 3]).unwrap();
+        let game = Game::new(board.clone(), vec![long, domino(0, 0)], vec![]);
+        assert!(!game.is_tileable_lower_bound());
+
+        let game = Game::new(board, vec![domino(0, 0), domino(1, 1)], vec![]);
+        assert!(game.is_tileable_lower_bound());
+    }
+
+    #[test]
+    fn validate_with_checks_tileability_on_request() {
+        // The cell counts match, but the two isolated cells touch nothing.
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.extend([Point::new(3, 3), Point::new(5, 5)]);
+        let pieces = vec![domino(1, 1), domino(2, 2), domino(3, 3)];
+        let game = Game::new(Board::new(board_points), pieces, vec![]);
+        game.validate().expect("cheap checks pass");
+        let options = ValidateOptions {
+            check_tileable: true,
+        };
+        let err = game.validate_with(&options).unwrap_err();
+        assert!(err.contains("No tiling exists"), "{}", err);
+
+        let square = Game::new(
+            Board::new(square_at(0, 0).into_iter().collect()),
+            vec![domino(1, 1), domino(2, 2)],
+            vec![],
+        );
+        square.validate_with(&options).expect("square tiles");
+    }
+
+    #[test]
+    fn split_components_gives_each_piece_to_exactly_one_component() {
+        // A 2x2 square and a 1x6 line. The square fills up with the O tetromino, so the domino,
+        // which fits either component and comes first, must go to the line with the I.
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.extend((3..9).map(|x| Point::new(x, 0)));
+        let board = Board::new(board_points);
+        let zeros = vec![Pips::new(0).unwrap(); 4];
+        let pieces = vec![
+            domino(1, 2),
+            Piece::new(PolyShape::TetO, zeros.clone()).unwrap(),
+            Piece::new(PolyShape::TetI, zeros).unwrap(),
+        ];
+        let game = Game::new(board, pieces.clone(), vec![]);
+
+        let parts = game.split_components().expect("components should split");
+        assert_eq!(parts.len(), 2);
+        for part in &parts {
+            part.validate().unwrap();
+        }
+        let mut assigned: Vec<Piece> = parts.iter().flat_map(|part| part.pieces.clone()).collect();
+        assert_eq!(assigned.len(), pieces.len());
+        assigned.sort_by_key(|piece| piece.to_string());
+        let mut expected = pieces;
+        expected.sort_by_key(|piece| piece.to_string());
+        assert_eq!(assigned, expected);
+        assert_eq!(parts[0].pieces.len(), 1);
+        assert!(parts[1].pieces.contains(&domino(1, 2)));
+    }
+
+    #[test]
+    fn split_components_rejects_ambiguous_piece_split() {
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.extend(square_at(5, 0));
+        let board = Board::new(board_points);
+        let pieces = vec![domino(1, 1), domino(2, 2), domino(3, 3), domino(4, 4)];
+        let game = Game::new(board, pieces, vec![]);
+        assert!(game.split_components().is_err());
+    }
+
+    #[test]
+    fn split_components_assigns_pieces_that_fit_one_component() {
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.extend((3..7).map(|x| Point::new(x, 0)));
+        let board = Board::new(board_points);
+        let zeros = vec![Pips::new(0).unwrap(); 4];
+        let line = Piece::new(PolyShape::TetI, zeros.clone()).unwrap();
+        let square = Piece::new(PolyShape::TetO, zeros).unwrap();
+        let game = Game::new(board, vec![line.clone(), square.clone()], vec![]);
+
+        let parts = game.split_components().expect("components should split");
+        assert_eq!(parts.len(), 2);
+        for part in &parts {
+            part.validate().unwrap();
+            assert_eq!(part.pieces.len(), 1);
+        }
+        let assigned: HashSet<Piece> = parts.iter().map(|part| part.pieces[0].clone()).collect();
+        assert_eq!(assigned, [line, square].into_iter().collect());
+    }
+
+    #[test]
+    fn split_components_rejects_straddling_constraint() {
+        let mut board_points: HashSet<Point> = square_at(0, 0).into_iter().collect();
+        board_points.extend(square_at(5, 0));
+        let board = Board::new(board_points);
+        let pieces = vec![domino(1, 1), domino(2, 2), domino(3, 3), domino(4, 4)];
+        let constraints = vec![Constraint::Exactly {
+            target: 4,
+            points: Arc::new([Point::new(1, 0), Point::new(5, 0)].into_iter().collect()),
+        }];
+        let game = Game::new(board, pieces, constraints);
+        assert!(game.split_components().is_err());
+    }
+    #[test]
+    fn constraint_density_counts_constrained_cells() {
+        let board = Board::new(square_at(0, 0).into_iter().collect());
+        let game = Game::new(board.clone(), vec![domino(1, 1), domino(2, 2)], vec![]);
+        assert_eq!(game.constraint_density(), 0.0);
+
+        let constraints = vec![Constraint::Exactly {
+            target: 3,
+            points: Arc::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect()),
+        }];
+        let game = Game::new(board, vec![domino(1, 1), domino(2, 2)], constraints);
+        assert!((game.constraint_density() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn structurally_eq_ignores_piece_and_constraint_order() {
+        let board = Board::new(
+            [(0, 0), (1, 0), (0, 1), (1, 1)]
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect(),
+        );
+        let a = domino(1, 2);
+        let b = domino(3, 4);
+        let top = Constraint::Exactly {
+            target: 3,
+            points: Arc::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect()),
+        };
+        let corner = Constraint::LessThan {
+            target: 5,
+            points: Arc::new([Point::new(1, 1)].into_iter().collect()),
+        };
+        let game = Game::new(
+            board.clone(),
+            vec![a.clone(), b.clone()],
+            vec![top.clone(), corner.clone()],
+        );
+        let reordered = Game::new(board.clone(), vec![b, a.clone()], vec![corner, top.clone()]);
+        assert_ne!(game, reordered);
+        assert!(game.structurally_eq(&reordered));
+
+        let duplicated = Game::new(board, vec![a.clone(), a], vec![top]);
+        assert!(!game.structurally_eq(&duplicated));
+    }
+
+    #[test]
+    fn hardest_cell_finds_the_dead_end() {
+        // A 3x2 block with a two-cell arm; (4,0) can only be covered from (3,0).
+        let mut points: HashSet<Point> = (0..2)
+            .flat_map(|y| (0..3).map(move |x| Point::new(x, y)))
+            .collect();
+        points.insert(Point::new(3, 0));
+        points.insert(Point::new(4, 0));
+        let pieces = vec![domino(1, 2), domino(3, 4), domino(5, 6), domino(0, 1)];
+        let game = Game::new(Board::new(points), pieces, vec![]);
+
+        assert_eq!(game.hardest_cell(), Some((Point::new(4, 0), 4)));
+        assert_eq!(
+            Game::new(Board::new(HashSet::new()), vec![], vec![]).hardest_cell(),
+            None
+        );
+    }
 }
 
 struct BoardComponent {
@@ -268,23 +834,6 @@ fn connected_components(board: &Board) -> Vec<BoardComponent> {
     components
 }
 
-fn orthogonal_neighbors(point: Point) -> Vec<Point> {
-    let mut neighbors = Vec::with_capacity(4);
-    if let Some(x) = point.x.checked_sub(1) {
-        neighbors.push(Point::new(x, point.y));
-    }
-    if let Some(x) = point.x.checked_add(1) {
-        neighbors.push(Point::new(x, point.y));
-    }
-    if let Some(y) = point.y.checked_sub(1) {
-        neighbors.push(Point::new(point.x, y));
-    }
-    if let Some(y) = point.y.checked_add(1) {
-        neighbors.push(Point::new(point.x, y));
-    }
-    neighbors
-}
-
 fn compare_points(a: Point, b: Point) -> Ordering {
     a.y.cmp(&b.y).then_with(|| a.x.cmp(&b.x))
 }
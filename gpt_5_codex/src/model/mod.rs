@@ -6,17 +6,20 @@ pub mod game;
 pub mod piece;
 pub mod pips;
 pub mod placement;
+pub mod play_state;
 pub mod point;
+pub mod prelude;
 
 #[allow(unused_imports)]
 pub use assignment::Assignment;
 #[allow(unused_imports)]
-pub use board::{Board, EMPTY_BOARD};
+pub use board::{Board, EMPTY_BOARD, Symmetry, SymmetrySet};
 pub use constraint::{Constraint, ConstraintSet, reduce_constraints};
 pub use direction::Direction;
 #[allow(unused_imports)]
-pub use game::{Game, WON_GAME};
+pub use game::{Game, ValidateOptions, WON_GAME};
 pub use piece::{Piece, PolyShape, remove_one};
 pub use pips::Pips;
 pub use placement::Placement;
+pub use play_state::PlayState;
 pub use point::Point;
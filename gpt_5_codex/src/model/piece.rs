@@ -1,8 +1,11 @@
+use super::direction::Direction;
 use super::pips::Pips;
 use once_cell::sync::Lazy;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
 use std::sync::Arc;
 
 const MONO_BASE: [(i32, i32); 1] = [(0, 0)];
@@ -64,6 +67,7 @@ struct ShapeDescriptor {
     code: &'static str,
     name: &'static str,
     orientations: &'static Lazy<Vec<Vec<(i32, i32)>>>,
+    reflect: bool,
     cell_count: usize,
 }
 
@@ -81,6 +85,7 @@ macro_rules! shape {
             code: $code,
             name: $name,
             orientations: &$orient,
+            reflect: $reflect,
             cell_count: $count,
         };
     };
@@ -96,6 +101,7 @@ macro_rules! shape_mirrored {
             code: $code,
             name: $name,
             orientations: &$orient,
+            reflect: false,
             cell_count: $count,
         };
     };
@@ -406,6 +412,90 @@ impl PolyShape {
         self.descriptor().orientations()
     }
 
+    /// Number of cell edges not shared with another cell of the shape, measured on the base
+    /// orientation. Compact shapes have less perimeter than stringy ones of the same size.
+    pub fn perimeter(&self) -> usize {
+        let cells: HashSet<(i32, i32)> = self.orientations()[0].iter().copied().collect();
+        cells
+            .iter()
+            .map(|&(x, y)| {
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .iter()
+                    .filter(|neighbor| !cells.contains(neighbor))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// SVG `d` path tracing the outline of the given orientation, with each
+    /// cell `cell_size` units wide and the shape's top-left corner at the origin, or `None` for
+    /// an out-of-range `orientation_index`.
+    pub fn svg_path(&self, orientation_index: usize, cell_size: f64) -> Option<String> {
+        let offsets = self.orientations().get(orientation_index)?;
+        let min_x = offsets.iter().map(|(x, _)| *x).min().unwrap_or(0);
+        let min_y = offsets.iter().map(|(_, y)| *y).min().unwrap_or(0);
+        let cells: HashSet<(i32, i32)> = offsets
+            .iter()
+            .map(|(x, y)| (x - min_x, y - min_y))
+            .collect();
+
+        // Clockwise boundary edges, keyed by their starting corner.
+        let mut edges: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
+        for &(x, y) in &cells {
+            let sides = [
+                ((x, y - 1), (x, y), (x + 1, y)),
+                ((x + 1, y), (x + 1, y), (x + 1, y + 1)),
+                ((x, y + 1), (x + 1, y + 1), (x, y + 1)),
+                ((x - 1, y), (x, y + 1), (x, y)),
+            ];
+            for (neighbor, from, to) in sides {
+                if !cells.contains(&neighbor) {
+                    edges.entry(from).or_default().push(to);
+                }
+            }
+        }
+
+        let mut commands = Vec::new();
+        while let Some(start) = edges
+            .iter()
+            .filter(|(_, ends)| !ends.is_empty())
+            .map(|(corner, _)| *corner)
+            .min_by_key(|&(x, y)| (y, x))
+        {
+            let mut corners = vec![start];
+            let mut current = start;
+            loop {
+                let ends = edges.get_mut(&current).expect("outline is closed");
+                let next = ends.pop().expect("outline is closed");
+                if next == start {
+                    break;
+                }
+                corners.push(next);
+                current = next;
+            }
+            let turns: Vec<(i32, i32)> = (0..corners.len())
+                .filter(|&i| {
+                    let prev = corners[(i + corners.len() - 1) % corners.len()];
+                    let next = corners[(i + 1) % corners.len()];
+                    let here = corners[i];
+                    (here.0 - prev.0) * (next.1 - here.1) != (here.1 - prev.1) * (next.0 - here.0)
+                })
+                .map(|i| corners[i])
+                .collect();
+            for (i, (x, y)) in turns.iter().enumerate() {
+                let verb = if i == 0 { 'M' } else { 'L' };
+                commands.push(format!(
+                    "{} {} {}",
+                    verb,
+                    *x as f64 * cell_size,
+                    *y as f64 * cell_size
+                ));
+            }
+            commands.push("Z".to_string());
+        }
+        Some(commands.join(" "))
+    }
+
     pub fn from_code(code: &str) -> Option<Self> {
         let normalized = code.trim().to_ascii_uppercase();
         match normalized.as_str() {
@@ -442,6 +532,59 @@ impl PolyShape {
         }
     }
 
+    /// Cell permutations induced by the rotations (and reflections, when the shape allows
+    /// them) that map the base orientation onto itself. The identity always comes first.
+    pub fn symmetry_permutations(&self) -> Vec<Vec<usize>> {
+        let base = &self.orientations()[0];
+        let base_signature = normalized_sorted(base);
+        let mut transforms = Vec::new();
+        let mut rotated = base.clone();
+        for _ in 0..4 {
+            transforms.push(rotated.clone());
+            if self.descriptor().reflect {
+                transforms.push(mirror_cells(&rotated));
+            }
+            rotated = rotate_cw(&rotated);
+        }
+
+        let mut permutations: Vec<Vec<usize>> = Vec::new();
+        for transformed in transforms {
+            if normalized_sorted(&transformed) != base_signature {
+                continue;
+            }
+            let transformed = normalize_preserve_order(&transformed);
+            let permutation: Vec<usize> = base
+                .iter()
+                .map(|cell| transformed.iter().position(|other| other == cell).unwrap())
+                .collect();
+            if !permutations.contains(&permutation) {
+                permutations.push(permutation);
+            }
+        }
+        permutations
+    }
+
+    /// Rotation (in degrees, matching `orientation_index_for_angle`) and mirroring that
+    /// turn the base orientation into the given one.
+    pub fn orientation_transform(&self, orientation_index: usize) -> Option<(u16, bool)> {
+        let orientations = self.orientations();
+        let target = normalized_sorted(orientations.get(orientation_index)?);
+        let base = &orientations[0];
+        for mirrored in [false, true] {
+            let start = if mirrored {
+                mirror_cells(base)
+            } else {
+                base.clone()
+            };
+            for angle in [0u16, 90, 180, 270] {
+                if normalized_sorted(&rotate_offsets(&start, angle)) == target {
+                    return Some((angle, mirrored));
+                }
+            }
+        }
+        None
+    }
+
     pub fn preferred_orientation_index(&self) -> usize {
         let orientations = self.orientations();
         if let Some(target) = canonical_target(self) {
@@ -466,6 +609,19 @@ impl PolyShape {
     }
 }
 
+impl FromStr for PolyShape {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PolyShape::from_code(s).ok_or_else(|| {
+            format!(
+                "Unknown shape code '{}' (expected e.g. 2I, 4T, 5Z-).",
+                s.trim()
+            )
+        })
+    }
+}
+
 fn canonical_target(shape: &PolyShape) -> Option<Vec<(i32, i32)>> {
     macro_rules! mirror_of {
         ($base:expr) => {{
@@ -565,12 +721,44 @@ fn rotate_point(x: i32, y: i32, angle: u16) -> (i32, i32) {
     }
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+/// Serializes as `{"shape": <code>, "pips": [..]}`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(into = "PieceRecord", try_from = "PieceRecord")]
 pub struct Piece {
     shape: PolyShape,
     pips: Arc<[Pips]>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct PieceRecord {
+    shape: String,
+    pips: Vec<u8>,
+}
+
+impl From<Piece> for PieceRecord {
+    fn from(piece: Piece) -> Self {
+        Self {
+            shape: piece.shape.code().to_string(),
+            pips: piece.pips.iter().map(|pips| pips.value()).collect(),
+        }
+    }
+}
+
+impl TryFrom<PieceRecord> for Piece {
+    type Error = String;
+
+    fn try_from(record: PieceRecord) -> Result<Self, Self::Error> {
+        let shape = PolyShape::from_code(&record.shape)
+            .ok_or_else(|| format!("Unsupported shape code '{}'.", record.shape))?;
+        let pips = record
+            .pips
+            .into_iter()
+            .map(Pips::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Piece::new(shape, pips)
+    }
+}
+
 impl Piece {
     pub fn new(shape: PolyShape, pips: Vec<Pips>) -> Result<Self, String> {
         if pips.len() != shape.cell_count() {
@@ -599,6 +787,11 @@ impl Piece {
         &self.pips
     }
 
+    /// The same shape carrying `pips` instead; fails if the count does not match the shape.
+    pub fn with_pips(&self, pips: Vec<Pips>) -> Result<Piece, String> {
+        Piece::new(self.shape, pips)
+    }
+
     pub fn orientations(&self) -> &'static [Vec<(i32, i32)>] {
         self.shape.orientations()
     }
@@ -607,8 +800,46 @@ impl Piece {
         self.shape.orientations().len()
     }
 
+    /// Direction from the first to the second cell of a domino laid in `orientation_index`,
+    /// or `None` for other shapes and out-of-range indices.
+    pub fn direction_for_orientation(
+        shape: PolyShape,
+        orientation_index: usize,
+    ) -> Option<Direction> {
+        if shape != PolyShape::Domino {
+            return None;
+        }
+        let cells = shape.orientations().get(orientation_index)?;
+        let step = (cells[1].0 - cells[0].0, cells[1].1 - cells[0].1);
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.delta() == step)
+    }
+
+    /// Distinct ways the piece's pips can land on the cells of a single orientation.
+    ///
+    /// Orientations are deduplicated by footprint, so a symmetric shape (a domino turned
+    /// half-way round, say) reaches its other pip layouts only through these arrangements.
+    /// Repeated pip values never produce duplicate arrangements.
     pub fn pip_permutations(&self) -> Vec<Vec<Pips>> {
-        vec![self.pips.to_vec()]
+        let mut arrangements: Vec<Vec<Pips>> = Vec::new();
+        for permutation in self.shape.symmetry_permutations() {
+            let arrangement: Vec<Pips> = permutation.iter().map(|&idx| self.pips[idx]).collect();
+            if !arrangements.contains(&arrangement) {
+                arrangements.push(arrangement);
+            }
+        }
+        arrangements
+    }
+
+    /// Inventory token: `a-b` (low value first) for dominoes, `code:pips` for other shapes.
+    pub fn display_face(&self) -> String {
+        if self.shape == PolyShape::Domino {
+            let (a, b) = (self.pips[0].value(), self.pips[1].value());
+            return format!("{}-{}", a.min(b), a.max(b));
+        }
+        let values: Vec<String> = self.pips.iter().map(|p| p.value().to_string()).collect();
+        format!("{}:{}", self.shape.code(), values.concat())
     }
 
     pub fn preferred_orientation_index(&self) -> usize {
@@ -731,6 +962,70 @@ mod tests {
         assert_ne!(idx0, idx180);
     }
 
+    fn pips(values: &[u8]) -> Vec<Pips> {
+        values
+            .iter()
+            .map(|&value| Pips::new(value).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn pip_permutations_follow_shape_symmetry() {
+        let domino = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        assert_eq!(
+            domino.pip_permutations(),
+            vec![pips(&[1, 2]), pips(&[2, 1])]
+        );
+
+        let double = Piece::domino(Pips::new(3).unwrap(), Pips::new(3).unwrap());
+        assert_eq!(double.pip_permutations(), vec![pips(&[3, 3])]);
+
+        let square = Piece::new(PolyShape::TetO, pips(&[1, 2, 3, 4])).unwrap();
+        assert_eq!(square.pip_permutations().len(), 4);
+
+        let tee = Piece::new(PolyShape::TetT, pips(&[1, 2, 3, 4])).unwrap();
+        assert_eq!(tee.pip_permutations().len(), 2);
+    }
+
+    #[test]
+    fn l_tromino_has_one_arrangement_per_orientation() {
+        // Every rotation of the L is already a distinct orientation, so no
+        // relabelling of [1,2,3] over a fixed footprint is reachable.
+        let piece = Piece::new(PolyShape::TriL, pips(&[1, 2, 3])).unwrap();
+        assert_eq!(PolyShape::TriL.orientations().len(), 4);
+        assert_eq!(piece.pip_permutations(), vec![pips(&[1, 2, 3])]);
+
+        let board: Vec<(i32, i32)> = vec![(0, 0), (0, 1), (1, 0)];
+        let mut layouts = Vec::new();
+        for offsets in piece.orientations() {
+            let mut footprint = offsets.clone();
+            footprint.sort();
+            if footprint != board {
+                continue;
+            }
+            for arrangement in piece.pip_permutations() {
+                let mut layout: Vec<((i32, i32), u8)> = offsets
+                    .iter()
+                    .zip(arrangement.iter())
+                    .map(|(&cell, pip)| (cell, pip.value()))
+                    .collect();
+                layout.sort();
+                // Corner cell must hold the 1.
+                if layout.contains(&((0, 0), 1)) && !layouts.contains(&layout) {
+                    layouts.push(layout);
+                }
+            }
+        }
+        assert_eq!(layouts.len(), 1);
+    }
+
+    #[test]
+    fn parses_shape_codes() {
+        assert_eq!("5Z-".parse::<PolyShape>(), Ok(PolyShape::PentZMinus));
+        let err = "9Q".parse::<PolyShape>().unwrap_err();
+        assert!(err.contains("9Q"));
+    }
+
     #[test]
     fn shape_has_two_orientations_for_line() {
         let orientations = PolyShape::TriI.orientations();
@@ -749,4 +1044,67 @@ mod tests {
         let result = Piece::new(PolyShape::TetI, vec![Pips::new(1).unwrap(); 4]);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn with_pips_keeps_shape_and_checks_length() {
+        let tee = Piece::new(PolyShape::TetT, pips(&[1, 2, 3, 4])).unwrap();
+        let rethemed = tee.with_pips(pips(&[6, 5, 4, 3])).unwrap();
+        assert_eq!(rethemed.shape(), PolyShape::TetT);
+        assert_eq!(rethemed.pips(), pips(&[6, 5, 4, 3]).as_slice());
+        assert!(tee.with_pips(pips(&[1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn json_rejects_unknown_shape_codes() {
+        let piece = Piece::new(PolyShape::TetO, vec![Pips::new(2).unwrap(); 4]).unwrap();
+        let json = serde_json::to_string(&piece).unwrap();
+        assert_eq!(serde_json::from_str::<Piece>(&json).unwrap(), piece);
+
+        let err = serde_json::from_str::<Piece>(r#"{"shape": "7Q", "pips": [1, 2]}"#).unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported shape code '7Q'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn perimeter_counts_exposed_edges() {
+        assert_eq!(PolyShape::Mono.perimeter(), 4);
+        assert_eq!(PolyShape::TetO.perimeter(), 8);
+        assert_eq!(PolyShape::TetI.perimeter(), 10);
+        assert_eq!(PolyShape::PentI.perimeter(), 12);
+    }
+
+    #[test]
+    fn svg_path_outlines_monomino_as_unit_square() {
+        assert_eq!(
+            PolyShape::Mono.svg_path(0, 1.0).as_deref(),
+            Some("M 0 0 L 1 0 L 1 1 L 0 1 Z")
+        );
+        assert_eq!(PolyShape::Mono.svg_path(1, 1.0), None);
+    }
+
+    #[test]
+    fn svg_path_spans_domino_extent() {
+        let coordinates = |path: &str| -> Vec<(f64, f64)> {
+            let numbers: Vec<f64> = path
+                .split_whitespace()
+                .filter_map(|token| token.parse().ok())
+                .collect();
+            numbers.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+        };
+        for (orientation, width, height) in [(0, 2.0, 1.0), (1, 1.0, 2.0)] {
+            let path = PolyShape::Domino
+                .svg_path(orientation, 10.0)
+                .expect("orientation in range");
+            let points = coordinates(&path);
+            assert_eq!(points.len(), 4, "{}", path);
+            let max_x = points.iter().map(|(x, _)| *x).fold(0.0, f64::max);
+            let max_y = points.iter().map(|(_, y)| *y).fold(0.0, f64::max);
+            assert_eq!((max_x, max_y), (width * 10.0, height * 10.0));
+            assert!(points.iter().all(|(x, y)| *x >= 0.0 && *y >= 0.0));
+            assert!(path.starts_with("M 0 0") && path.ends_with('Z'));
+        }
+    }
 }
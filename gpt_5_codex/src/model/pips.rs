@@ -26,6 +26,25 @@ impl Pips {
     pub fn value(self) -> u8 {
         self.0
     }
+
+    /// The value widened for sums; always within `MIN..=MAX`, so sums of up to
+    /// `u32::MAX / MAX` pips cannot overflow.
+    pub fn value_u32(self) -> u32 {
+        u32::from(self.0)
+    }
+
+    /// Unicode die face for the value (⚀–⚅), with a hollow square standing in for a blank.
+    pub fn as_die_face(self) -> char {
+        match self.0 {
+            1 => '⚀',
+            2 => '⚁',
+            3 => '⚂',
+            4 => '⚃',
+            5 => '⚄',
+            6 => '⚅',
+            _ => '□',
+        }
+    }
 }
 
 impl fmt::Display for Pips {
@@ -62,4 +81,20 @@ mod tests {
         assert!(Pips::new(7).is_err());
         assert!(Pips::new(255).is_err());
     }
+
+    #[test]
+    fn maps_each_value_to_its_die_face() {
+        let faces: Vec<char> = (Pips::MIN..=Pips::MAX)
+            .map(|value| Pips::new(value).unwrap().as_die_face())
+            .collect();
+        assert_eq!(faces, vec!['□', '⚀', '⚁', '⚂', '⚃', '⚄', '⚅']);
+    }
+
+    #[test]
+    fn value_u32_widens_value() {
+        for value in Pips::MIN..=Pips::MAX {
+            let pips = Pips::new(value).unwrap();
+            assert_eq!(pips.value_u32(), pips.value() as u32);
+        }
+    }
 }
@@ -1,8 +1,17 @@
-use super::{assignment::Assignment, piece::Piece, pips::Pips, point::Point};
+use super::{
+    assignment::Assignment,
+    direction::Direction,
+    piece::{Piece, PolyShape},
+    pips::Pips,
+    point::Point,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Places a polyomino piece at an anchor with a chosen orientation.
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// Places a polyomino piece at an anchor with a chosen orientation. Serializes as the piece,
+/// the anchor as `[x, y]`, the orientation index, and the pips in placement order.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(into = "PlacementRecord", try_from = "PlacementRecord")]
 pub struct Placement {
     pub piece: Piece,
     pub anchor: Point,
@@ -45,6 +54,125 @@ impl Placement {
     pub fn points(&self) -> Vec<Point> {
         self.assignments().into_iter().map(|a| a.point).collect()
     }
+
+    pub fn pip_at(&self, point: Point) -> Option<Pips> {
+        self.assignments()
+            .into_iter()
+            .find(|assignment| assignment.point == point)
+            .map(|assignment| assignment.pips)
+    }
+
+    /// Human-readable move description. Dominoes use the playout format from the README,
+    /// `(a, b) @ (x, y) heading Dir`, where the heading points from the first pip to the
+    /// second and `(x, y)` is the top-left cell; other shapes print their rotation.
+    pub fn describe_playout(&self) -> String {
+        let assignments = self.assignments();
+        if self.piece.shape() == PolyShape::Domino && assignments.len() == 2 {
+            let (first, second) = (&assignments[0], &assignments[1]);
+            let heading = match (
+                second.point.x as i64 - first.point.x as i64,
+                second.point.y as i64 - first.point.y as i64,
+            ) {
+                (1, 0) => Direction::East,
+                (-1, 0) => Direction::West,
+                (0, -1) => Direction::North,
+                _ => Direction::South,
+            };
+            let corner = Point::new(
+                first.point.x.min(second.point.x),
+                first.point.y.min(second.point.y),
+            );
+            return format!(
+                "({}, {}) @ ({}, {}) heading {}",
+                first.pips.value(),
+                second.pips.value(),
+                corner.x,
+                corner.y,
+                heading
+            );
+        }
+
+        let values: Vec<String> = self
+            .pip_order
+            .iter()
+            .map(|pip| pip.value().to_string())
+            .collect();
+        let transform = match self
+            .piece
+            .shape()
+            .orientation_transform(self.orientation_index)
+        {
+            Some((angle, false)) => format!("rot {}°", angle),
+            Some((angle, true)) => format!("rot {}° mirrored", angle),
+            None => format!("orientation {}", self.orientation_index),
+        };
+        format!(
+            "{} [{}] @ ({}, {}) {}",
+            self.piece.shape().code(),
+            values.join(","),
+            self.anchor.x,
+            self.anchor.y,
+            transform
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlacementRecord {
+    piece: Piece,
+    anchor: Point,
+    orientation: usize,
+    pips: Vec<u8>,
+}
+
+impl From<Placement> for PlacementRecord {
+    fn from(placement: Placement) -> Self {
+        Self {
+            pips: placement
+                .pip_order
+                .iter()
+                .map(|pips| pips.value())
+                .collect(),
+            piece: placement.piece,
+            anchor: placement.anchor,
+            orientation: placement.orientation_index,
+        }
+    }
+}
+
+impl TryFrom<PlacementRecord> for Placement {
+    type Error = String;
+
+    fn try_from(record: PlacementRecord) -> Result<Self, Self::Error> {
+        let piece = record.piece;
+        if record.orientation >= piece.orientation_count() {
+            return Err(format!(
+                "Orientation {} is out of range for piece {}.",
+                record.orientation, piece
+            ));
+        }
+        let pip_order = record
+            .pips
+            .iter()
+            .map(|&value| Pips::new(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut sorted_order = pip_order.clone();
+        sorted_order.sort();
+        let mut sorted_piece = piece.pips().to_vec();
+        sorted_piece.sort();
+        if sorted_order != sorted_piece {
+            return Err(format!(
+                "Pip order {:?} does not match piece {}.",
+                record.pips, piece
+            ));
+        }
+        Ok(Placement::new(
+            piece,
+            record.anchor,
+            record.orientation,
+            pip_order,
+        ))
+    }
 }
 
 impl fmt::Display for Placement {
@@ -70,4 +198,68 @@ mod tests {
         let assignments = placement.assignments();
         assert_eq!(assignments.len(), 3);
     }
+
+    #[test]
+    fn pip_at_reports_covered_cells_only() {
+        let piece = Piece::domino(Pips::new(2).unwrap(), Pips::new(5).unwrap());
+        let pip_order = piece.pips().to_vec();
+        let placement = Placement::new(piece, Point::new(1, 1), 0, pip_order);
+        assert_eq!(
+            placement.pip_at(Point::new(1, 1)),
+            Some(Pips::new(2).unwrap())
+        );
+        assert_eq!(
+            placement.pip_at(Point::new(2, 1)),
+            Some(Pips::new(5).unwrap())
+        );
+        assert_eq!(placement.pip_at(Point::new(0, 0)), None);
+    }
+
+    #[test]
+    fn describe_playout_matches_domino_exemplar_format() {
+        let piece = Piece::domino(Pips::new(2).unwrap(), Pips::new(4).unwrap());
+        // Orientation 1 stacks the first pip below the second.
+        let pip_order = vec![Pips::new(2).unwrap(), Pips::new(4).unwrap()];
+        let placement = Placement::new(piece.clone(), Point::new(0, 3), 1, pip_order);
+        assert_eq!(
+            placement.describe_playout(),
+            "(2, 4) @ (0, 3) heading North"
+        );
+
+        let pip_order = vec![Pips::new(2).unwrap(), Pips::new(4).unwrap()];
+        let placement = Placement::new(piece, Point::new(1, 1), 0, pip_order);
+        assert_eq!(placement.describe_playout(), "(2, 4) @ (1, 1) heading East");
+    }
+
+    #[test]
+    fn describe_playout_reports_pentomino_rotation() {
+        let values: Vec<Pips> = (1..=5).map(|v| Pips::new(v).unwrap()).collect();
+        let piece = Piece::new(PolyShape::PentI, values.clone()).unwrap();
+        let placement = Placement::new(piece, Point::new(2, 0), 1, values);
+        assert_eq!(
+            placement.describe_playout(),
+            "5I [1,2,3,4,5] @ (2, 0) rot 90°"
+        );
+    }
+
+    #[test]
+    fn serde_rejects_out_of_range_orientation() {
+        let piece = Piece::domino(Pips::new(2).unwrap(), Pips::new(4).unwrap());
+        let placement = Placement::new(
+            piece,
+            Point::new(1, 2),
+            1,
+            vec![Pips::new(4).unwrap(), Pips::new(2).unwrap()],
+        );
+        let json = serde_json::to_string(&placement).unwrap();
+        assert_eq!(
+            json,
+            r#"{"piece":{"shape":"2I","pips":[2,4]},"anchor":[1,2],"orientation":1,"pips":[4,2]}"#
+        );
+        assert_eq!(serde_json::from_str::<Placement>(&json).unwrap(), placement);
+
+        let bad = json.replace(r#""orientation":1"#, r#""orientation":9"#);
+        let err = serde_json::from_str::<Placement>(&bad).unwrap_err();
+        assert!(err.to_string().contains("Orientation 9"), "{}", err);
+    }
 }
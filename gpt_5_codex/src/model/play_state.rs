@@ -0,0 +1,249 @@
+use super::{
+    board::Board, constraint::Constraint, game::Game, piece::Piece, pips::Pips,
+    placement::Placement, point::Point,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A game together with the placements played on it so far, for saving and resuming an
+/// interactive session. Serializes as plain JSON-friendly records (points as `[x, y]`,
+/// pieces as shape code plus pips) rather than the model's internal layout.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(into = "PlayStateRecord", try_from = "PlayStateRecord")]
+pub struct PlayState {
+    pub game: Game,
+    pub played: Vec<Placement>,
+}
+
+impl PlayState {
+    pub fn new(game: Game, played: Vec<Placement>) -> Self {
+        Self { game, played }
+    }
+
+    /// Checks that the played prefix could actually have been played: every cell it covers
+    /// is on the board and covered once, each piece comes out of the inventory, and no
+    /// constraint is violated along the way.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut covered: HashSet<Point> = HashSet::new();
+        for placement in &self.played {
+            for point in placement.points() {
+                if !self.game.board.contains_point(&point) {
+                    return Err(format!(
+                        "Played placement {} covers {}, which is not on the board.",
+                        placement, point
+                    ));
+                }
+                if !covered.insert(point) {
+                    return Err(format!(
+                        "Played placement {} covers {}, which is already covered.",
+                        placement, point
+                    ));
+                }
+            }
+        }
+        self.game.apply_placements(&self.played).map(|_| ())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PlayStateRecord {
+    board: Vec<Point>,
+    pieces: Vec<Piece>,
+    constraints: Vec<ConstraintRecord>,
+    played: Vec<Placement>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum ConstraintRecord {
+    AllSame {
+        expected: Option<u8>,
+        points: Vec<Point>,
+    },
+    AllDifferent {
+        excluded: Vec<u8>,
+        points: Vec<Point>,
+    },
+    Exactly {
+        target: u32,
+        points: Vec<Point>,
+    },
+    LessThan {
+        target: u32,
+        points: Vec<Point>,
+    },
+    MoreThan {
+        target: u32,
+        points: Vec<Point>,
+    },
+}
+
+impl From<PlayState> for PlayStateRecord {
+    fn from(state: PlayState) -> Self {
+        let mut board: Vec<Point> = state.game.board.iter().collect();
+        board.sort();
+        Self {
+            board,
+            constraints: state
+                .game
+                .constraints
+                .iter()
+                .map(ConstraintRecord::from)
+                .collect(),
+            pieces: state.game.pieces,
+            played: state.played,
+        }
+    }
+}
+
+impl TryFrom<PlayStateRecord> for PlayState {
+    type Error = String;
+
+    fn try_from(record: PlayStateRecord) -> Result<Self, Self::Error> {
+        let board = Board::new(record.board.into_iter().collect());
+        let constraints = record
+            .constraints
+            .into_iter()
+            .map(Constraint::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PlayState::new(
+            Game::new(board, record.pieces, constraints),
+            record.played,
+        ))
+    }
+}
+
+impl From<&Constraint> for ConstraintRecord {
+    fn from(constraint: &Constraint) -> Self {
+        let points = points_record(constraint.points());
+        match constraint {
+            Constraint::AllSame { expected, .. } => ConstraintRecord::AllSame {
+                expected: expected.map(Pips::value),
+                points,
+            },
+            Constraint::AllDifferent { excluded, .. } => {
+                let mut excluded: Vec<u8> = excluded.iter().map(|pips| pips.value()).collect();
+                excluded.sort_unstable();
+                ConstraintRecord::AllDifferent { excluded, points }
+            }
+            Constraint::Exactly { target, .. } => ConstraintRecord::Exactly {
+                target: *target,
+                points,
+            },
+            Constraint::LessThan { target, .. } => ConstraintRecord::LessThan {
+                target: *target,
+                points,
+            },
+            Constraint::MoreThan { target, .. } => ConstraintRecord::MoreThan {
+                target: *target,
+                points,
+            },
+        }
+    }
+}
+
+impl TryFrom<ConstraintRecord> for Constraint {
+    type Error = String;
+
+    fn try_from(record: ConstraintRecord) -> Result<Self, Self::Error> {
+        let points_from =
+            |points: Vec<Point>| -> Arc<HashSet<Point>> { Arc::new(points.into_iter().collect()) };
+        Ok(match record {
+            ConstraintRecord::AllSame { expected, points } => Constraint::AllSame {
+                expected: expected.map(Pips::new).transpose()?,
+                points: points_from(points),
+            },
+            ConstraintRecord::AllDifferent { excluded, points } => Constraint::AllDifferent {
+                excluded: Arc::new(pips_from(&excluded)?.into_iter().collect()),
+                points: points_from(points),
+            },
+            ConstraintRecord::Exactly { target, points } => Constraint::Exactly {
+                target,
+                points: points_from(points),
+            },
+            ConstraintRecord::LessThan { target, points } => Constraint::LessThan {
+                target,
+                points: points_from(points),
+            },
+            ConstraintRecord::MoreThan { target, points } => Constraint::MoreThan {
+                target,
+                points: points_from(points),
+            },
+        })
+    }
+}
+
+fn points_record(points: &HashSet<Point>) -> Vec<Point> {
+    let mut sorted: Vec<Point> = points.iter().copied().collect();
+    sorted.sort();
+    sorted
+}
+
+fn pips_from(values: &[u8]) -> Result<Vec<Pips>, String> {
+    values.iter().map(|&value| Pips::new(value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlayState;
+    use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point};
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    fn domino(a: u8, b: u8) -> Piece {
+        Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap())
+    }
+
+    fn two_by_two() -> Game {
+        let board = Board::new(
+            [(0, 0), (1, 0), (0, 1), (1, 1)]
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect(),
+        );
+        let constraints = vec![Constraint::Exactly {
+            target: 3,
+            points: Arc::new(HashSet::from([Point::new(0, 0), Point::new(1, 0)])),
+        }];
+        Game::new(board, vec![domino(1, 2), domino(3, 4)], constraints)
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let game = two_by_two();
+        let piece = game.pieces[0].clone();
+        let played = vec![Placement::new(
+            piece,
+            Point::new(0, 0),
+            0,
+            vec![Pips::new(2).unwrap(), Pips::new(1).unwrap()],
+        )];
+        let state = PlayState::new(game, played);
+        state.validate().expect("played prefix is consistent");
+
+        let json = serde_json::to_string(&state).expect("serializes");
+        let restored: PlayState = serde_json::from_str(&json).expect("deserializes");
+        assert_eq!(restored, state);
+    }
+
+    #[test]
+    fn rejects_overlapping_played_prefix() {
+        let game = two_by_two();
+        let first = Placement::new(
+            game.pieces[0].clone(),
+            Point::new(0, 0),
+            0,
+            vec![Pips::new(1).unwrap(), Pips::new(2).unwrap()],
+        );
+        let second = Placement::new(
+            game.pieces[1].clone(),
+            Point::new(1, 0),
+            1,
+            vec![Pips::new(3).unwrap(), Pips::new(4).unwrap()],
+        );
+        let state = PlayState::new(game, vec![first, second]);
+        let err = state.validate().expect_err("placements overlap");
+        assert!(err.contains("(1, 0), which is already covered"), "{}", err);
+    }
+}
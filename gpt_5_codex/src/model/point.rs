@@ -1,4 +1,6 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::str::FromStr;
 
 /// Represents a coordinate on the board grid.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -11,6 +13,63 @@ impl Point {
     pub fn new(x: u32, y: u32) -> Self {
         Self { x, y }
     }
+
+    /// Formats the point in the loader's compact `(x,y)` syntax, which `FromStr` parses back.
+    pub fn to_compact(&self) -> String {
+        format!("({},{})", self.x, self.y)
+    }
+
+    /// Parses the `x,y` between a point's parentheses, as written in puzzle files.
+    pub(crate) fn from_coordinates(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split(',');
+        let x = parts
+            .next()
+            .ok_or_else(|| "Point is missing an x coordinate.".to_string())?;
+        let y = parts
+            .next()
+            .ok_or_else(|| "Point is missing a y coordinate.".to_string())?;
+        if parts.next().is_some() {
+            return Err("Point has too many coordinates.".to_string());
+        }
+        let x_val = x
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid x coordinate '{}'.", x))?;
+        let y_val = y
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid y coordinate '{}'.", y))?;
+        Ok(Self::new(x_val, y_val))
+    }
+}
+
+/// Accepts the loader's `(x,y)` syntax, with or without spaces, so both `to_compact` and
+/// `Display` output parse back.
+impl FromStr for Point {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| format!("Point '{}' must be written as (x,y).", s))?;
+        Self::from_coordinates(inner)
+    }
+}
+
+/// Serializes as a two-element `[x, y]` array.
+impl Serialize for Point {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y].serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Point {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y] = <[u32; 2]>::deserialize(deserializer)?;
+        Ok(Self::new(x, y))
+    }
 }
 
 impl fmt::Display for Point {
@@ -29,4 +88,24 @@ mod tests {
         assert_eq!(p.x, 1);
         assert_eq!(p.y, 2);
     }
+
+    #[test]
+    fn round_trips_through_json_array() {
+        let point = Point::new(3, 7);
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(json, "[3,7]");
+        assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), point);
+        assert!(serde_json::from_str::<Point>("[3]").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_string_form() {
+        let point = Point::new(12, 0);
+        assert_eq!(point.to_compact(), "(12,0)");
+        assert_eq!(point.to_compact().parse::<Point>(), Ok(point));
+        assert_eq!(point.to_string(), "(12, 0)");
+        assert_eq!(point.to_string().parse::<Point>(), Ok(point));
+        assert!("12,0".parse::<Point>().is_err());
+        assert!("(1,2,3)".parse::<Point>().is_err());
+    }
 }
@@ -0,0 +1,24 @@
+//! The commonly used model types and helpers, for `use pips_solver::model::prelude::*;`.
+
+pub use super::{
+    Assignment, Board, Constraint, ConstraintSet, Direction, Game, Piece, Pips, Placement,
+    PlayState, Point, PolyShape, reduce_constraints, remove_one,
+};
+
+#[cfg(test)]
+mod tests {
+    use crate::model::prelude::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn prelude_builds_a_game() {
+        let points: HashSet<Point> = [Point::new(0, 0), Point::new(1, 0)].into_iter().collect();
+        let piece = Piece::domino(Pips::new(2).unwrap(), Pips::new(3).unwrap());
+        let constraints =
+            vec![Constraint::exactly_from(5, &[Point::new(0, 0), Point::new(1, 0)]).unwrap()];
+        let game = Game::new(Board::new(points), vec![piece.clone()], constraints);
+        game.validate().unwrap();
+        assert!(remove_one(game.pieces.clone(), &piece).unwrap().is_empty());
+        assert_eq!(PolyShape::Domino.cell_count(), 2);
+    }
+}
@@ -1,9 +1,9 @@
-use crate::model::{Board, Point};
+use crate::model::{Board, Pips, Point};
 use crate::polypips::rules::{
     ConstraintRule, ConstraintSelection, PieceRule, parse_constraint_rule,
     parse_constraint_selection, parse_piece_rule,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub struct GeneratorConfig {
     pub board: Board,
@@ -12,6 +12,45 @@ pub struct GeneratorConfig {
     pub coverage: f64,
     pub selection: ConstraintSelection,
     pub seed: Option<u64>,
+    /// Board cells whose pip value is pinned; constraints are generated around them.
+    pub fixed_cells: HashMap<Point, Pips>,
+    pub tiling_order: TilingOrder,
+}
+
+/// Which open cell the tiling search covers next.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TilingOrder {
+    /// The top-most, then left-most open cell.
+    #[default]
+    TopLeft,
+    /// The open cell with the fewest open neighbours, so pockets and corners that only fit a
+    /// few placements get filled before they can be cut off.
+    InteriorFirst,
+}
+
+/// Parses a `fixed-cell` value, `x,y=pips`.
+fn parse_fixed_cell(value: &str) -> Result<(Point, Pips), String> {
+    let (point, pips) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid fixed-cell '{}'. Expected x,y=pips.", value))?;
+    let point = Point::from_coordinates(point)?;
+    let pips = pips
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| format!("Invalid fixed-cell pips '{}'.", pips.trim()))
+        .and_then(Pips::new)?;
+    Ok((point, pips))
+}
+
+fn parse_tiling_order(value: &str) -> Result<TilingOrder, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "top-left" => Ok(TilingOrder::TopLeft),
+        "interior-first" => Ok(TilingOrder::InteriorFirst),
+        other => Err(format!(
+            "Unknown tiling-order '{}'. Expected top-left or interior-first.",
+            other
+        )),
+    }
 }
 
 pub fn parse_config(contents: &str) -> Result<GeneratorConfig, String> {
@@ -32,6 +71,8 @@ pub fn parse_config(contents: &str) -> Result<GeneratorConfig, String> {
     let mut coverage: Option<f64> = None;
     let mut selection: Option<String> = None;
     let mut seed: Option<u64> = None;
+    let mut tiling_order = TilingOrder::default();
+    let mut fixed_cells = HashMap::new();
 
     while let Some(line) = lines.next() {
         let trimmed = line.trim();
@@ -65,9 +106,16 @@ pub fn parse_config(contents: &str) -> Result<GeneratorConfig, String> {
                     .map_err(|_| format!("Invalid seed '{}'. Expected an integer.", value))?;
                 seed = Some(parsed);
             }
+            "tiling-order" => tiling_order = parse_tiling_order(value)?,
+            "fixed-cell" => {
+                let (point, pips) = parse_fixed_cell(value)?;
+                if fixed_cells.insert(point, pips).is_some() {
+                    return Err(format!("Cell {} is fixed more than once.", point));
+                }
+            }
             other => {
                 return Err(format!(
-                    "Unknown configuration key '{}'. Expected pieces, constraints, constraint-coverage, constraint-selection, or seed.",
+                    "Unknown configuration key '{}'. Expected pieces, constraints, constraint-coverage, constraint-selection, seed, tiling-order, or fixed-cell.",
                     other
                 ));
             }
@@ -98,6 +146,8 @@ pub fn parse_config(contents: &str) -> Result<GeneratorConfig, String> {
         coverage,
         selection,
         seed,
+        fixed_cells,
+        tiling_order,
     })
 }
 
@@ -167,3 +217,32 @@ fn parse_board(lines: &[String]) -> Result<Board, String> {
     }
     Ok(Board::new(points))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_config;
+    use crate::model::{Pips, Point};
+
+    #[test]
+    fn parses_repeated_fixed_cells() {
+        let config = parse_config(
+            "board:\n###\n###\n\npieces: 2*\nfixed-cell: 1,0=5\nfixed-cell: 2, 1 = 0\n",
+        )
+        .expect("config parses");
+        assert_eq!(config.fixed_cells.len(), 2);
+        assert_eq!(
+            config.fixed_cells.get(&Point::new(1, 0)),
+            Some(&Pips::new(5).unwrap())
+        );
+        assert_eq!(
+            config.fixed_cells.get(&Point::new(2, 1)),
+            Some(&Pips::new(0).unwrap())
+        );
+
+        assert!(parse_config("board:\n##\n\npieces: 2*\nfixed-cell: 0,0=9\n").is_err());
+        assert!(
+            parse_config("board:\n##\n\npieces: 2*\nfixed-cell: 0,0=1\nfixed-cell: 0,0=2\n")
+                .is_err()
+        );
+    }
+}
@@ -1,6 +1,8 @@
+use crate::model::board::orthogonal_neighbors;
 use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point, PolyShape};
-use crate::polypips::config::GeneratorConfig;
+use crate::polypips::config::{GeneratorConfig, TilingOrder};
 use crate::polypips::rules::{ConstraintRule, ConstraintSelection, PieceRule};
+use crate::util::math::gcd;
 use crate::util::rng::SimpleRng;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -27,13 +29,32 @@ pub fn generate(config: GeneratorConfig) -> Result<GeneratedPuzzle, String> {
     let (width, height) = board_dimensions(&board_points)?;
     let mut rng = SimpleRng::new(config.seed, width as u64, height as u64);
 
-    let piece_specs = tile_board(&board_points, &config.piece_rule, &mut rng)?;
+    if let Some(point) = config
+        .fixed_cells
+        .keys()
+        .find(|point| !board_points.contains(point))
+    {
+        return Err(format!("Fixed cell {} is not on the board.", point));
+    }
+
+    let piece_specs = tile_board(
+        &board_points,
+        &config.piece_rule,
+        config.tiling_order,
+        &mut rng,
+    )?;
 
     let constraint_specs = place_constraints(&board_points, &config, &mut rng)?;
 
-    let (constraints, mut board_pips) = assign_constraints(&constraint_specs, &mut rng)?;
+    let (constraints, mut board_pips) =
+        assign_constraints(&constraint_specs, &config.fixed_cells, &mut rng)?;
 
-    fill_remaining_cells(&board_points, &mut board_pips, &mut rng)?;
+    fill_remaining_cells(
+        &board_points,
+        &config.fixed_cells,
+        &mut board_pips,
+        &mut rng,
+    )?;
 
     let (pieces, placements) = materialize_pieces(&piece_specs, &board_pips)?;
 
@@ -93,16 +114,17 @@ impl ShapeRequirement {
 fn tile_board(
     board_points: &HashSet<Point>,
     rule: &PieceRule,
+    order: TilingOrder,
     rng: &mut SimpleRng,
 ) -> Result<Vec<PlacementSpec>, String> {
     match rule {
-        PieceRule::Unlimited(shapes) => tile_unlimited(board_points, shapes, rng),
+        PieceRule::Unlimited(shapes) => tile_unlimited(board_points, shapes, order, rng),
         PieceRule::Exact(shapes) => {
             let requirements: Vec<ShapeRequirement> = shapes
                 .iter()
                 .map(|shape| ShapeRequirement::single(*shape))
                 .collect();
-            tile_exact(board_points, requirements, rng)
+            tile_exact(board_points, requirements, order, rng)
         }
         PieceRule::ExactPentominoSet => {
             let board_area = board_points.len();
@@ -112,7 +134,7 @@ fn tile_board(
                 );
             }
             let requirements = build_pentomino_requirements();
-            tile_exact(board_points, requirements, rng)
+            tile_exact(board_points, requirements, order, rng)
         }
     }
 }
@@ -120,22 +142,23 @@ fn tile_board(
 fn tile_unlimited(
     board_points: &HashSet<Point>,
     shapes: &[PolyShape],
+    order: TilingOrder,
     rng: &mut SimpleRng,
 ) -> Result<Vec<PlacementSpec>, String> {
     if shapes.is_empty() {
         return Err("Pieces rule resolved to an empty shape set.".to_string());
     }
-    let gcd = shapes
+    let size_gcd = shapes
         .iter()
         .map(|shape| shape.cell_count())
-        .fold(0usize, gcd_usize);
-    if gcd == 0 || board_points.len() % gcd != 0 {
+        .fold(0usize, gcd);
+    if size_gcd == 0 || !board_points.len().is_multiple_of(size_gcd) {
         return Err("Board area is incompatible with the chosen piece shapes.".to_string());
     }
 
     let mut available = board_points.clone();
     let mut placements = Vec::new();
-    if backtrack_unlimited(&mut available, &mut placements, shapes, rng) {
+    if backtrack_unlimited(&mut available, &mut placements, shapes, order, rng) {
         Ok(placements)
     } else {
         Err("Failed to tile the board with the allowed shapes.".to_string())
@@ -145,6 +168,7 @@ fn tile_unlimited(
 fn tile_exact(
     board_points: &HashSet<Point>,
     requirements: Vec<ShapeRequirement>,
+    order: TilingOrder,
     rng: &mut SimpleRng,
 ) -> Result<Vec<PlacementSpec>, String> {
     if requirements.is_empty() {
@@ -159,7 +183,7 @@ fn tile_exact(
     rng.shuffle(&mut ordered);
     let mut available = board_points.clone();
     let mut placements = Vec::new();
-    if backtrack_exact(&mut available, &mut placements, &mut ordered, rng) {
+    if backtrack_exact(&mut available, &mut placements, &mut ordered, order, rng) {
         Ok(placements)
     } else {
         Err("Failed to tile the board with the requested exact shapes.".to_string())
@@ -183,16 +207,22 @@ fn build_pentomino_requirements() -> Vec<ShapeRequirement> {
     ]
 }
 
+#[cfg(test)]
+thread_local! {
+    static TILING_BACKTRACKS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 fn backtrack_unlimited(
     available: &mut HashSet<Point>,
     placements: &mut Vec<PlacementSpec>,
     shapes: &[PolyShape],
+    order: TilingOrder,
     rng: &mut SimpleRng,
 ) -> bool {
     if available.is_empty() {
         return true;
     }
-    let pivot = pick_pivot(available);
+    let pivot = pick_pivot(available, order);
 
     let mut shape_order: Vec<PolyShape> = shapes.to_vec();
     rng.shuffle(&mut shape_order);
@@ -214,9 +244,11 @@ fn backtrack_unlimited(
                     for cell in &cells {
                         available.remove(cell);
                     }
-                    if backtrack_unlimited(available, placements, shapes, rng) {
+                    if backtrack_unlimited(available, placements, shapes, order, rng) {
                         return true;
                     }
+                    #[cfg(test)]
+                    TILING_BACKTRACKS.with(|count| count.set(count.get() + 1));
                     for cell in cells {
                         available.insert(cell);
                     }
@@ -232,6 +264,7 @@ fn backtrack_exact(
     available: &mut HashSet<Point>,
     placements: &mut Vec<PlacementSpec>,
     requirements: &mut Vec<ShapeRequirement>,
+    order: TilingOrder,
     rng: &mut SimpleRng,
 ) -> bool {
     if requirements.is_empty() {
@@ -241,7 +274,7 @@ fn backtrack_exact(
         return false;
     }
 
-    let pivot = pick_pivot(available);
+    let pivot = pick_pivot(available, order);
     let mut requirement_indices: Vec<usize> = (0..requirements.len()).collect();
     rng.shuffle(&mut requirement_indices);
 
@@ -271,9 +304,11 @@ fn backtrack_exact(
                             available.remove(cell);
                         }
 
-                        if backtrack_exact(available, placements, requirements, rng) {
+                        if backtrack_exact(available, placements, requirements, order, rng) {
                             return true;
                         }
+                        #[cfg(test)]
+                        TILING_BACKTRACKS.with(|count| count.set(count.get() + 1));
 
                         for cell in cells {
                             available.insert(cell);
@@ -290,11 +325,20 @@ fn backtrack_exact(
     false
 }
 
-fn pick_pivot(available: &HashSet<Point>) -> Point {
+fn pick_pivot(available: &HashSet<Point>, order: TilingOrder) -> Point {
+    let open_neighbors = |point: &Point| {
+        orthogonal_neighbors(*point)
+            .iter()
+            .filter(|neighbor| available.contains(neighbor))
+            .count()
+    };
     available
         .iter()
         .copied()
-        .min_by_key(|point| (point.y, point.x))
+        .min_by_key(|point| match order {
+            TilingOrder::TopLeft => (0, point.y, point.x),
+            TilingOrder::InteriorFirst => (open_neighbors(point), point.y, point.x),
+        })
         .expect("available set not empty")
 }
 
@@ -508,6 +552,7 @@ fn constraint_cells(
 
 fn assign_constraints(
     specs: &[ConstraintSpec],
+    fixed_cells: &HashMap<Point, Pips>,
     rng: &mut SimpleRng,
 ) -> Result<(Vec<Constraint>, HashMap<Point, Pips>), String> {
     let mut constraints = Vec::new();
@@ -521,7 +566,7 @@ fn assign_constraints(
             let y = (spec.anchor.y as i32 + dy) as u32;
             points.push(Point::new(x, y));
         }
-        let (constraint, assignments) = generate_constraint(points, rng)?;
+        let (constraint, assignments) = generate_constraint(points, fixed_cells, rng)?;
         for (point, pip) in &assignments {
             board_pips.insert(*point, *pip);
         }
@@ -533,20 +578,36 @@ fn assign_constraints(
 
 fn generate_constraint(
     points: Vec<Point>,
+    fixed_cells: &HashMap<Point, Pips>,
     rng: &mut SimpleRng,
 ) -> Result<(Constraint, Vec<(Point, Pips)>), String> {
-    let mut choices = vec![
-        ConstraintKind::AllSame,
-        ConstraintKind::Exactly,
-        ConstraintKind::LessThan,
-        ConstraintKind::MoreThan,
-    ];
-    if points.len() > 1 && points.len() <= (Pips::MAX as usize + 1) {
+    let pinned: Vec<Pips> = points
+        .iter()
+        .filter_map(|point| fixed_cells.get(point).copied())
+        .collect();
+    let free = points.len() - pinned.len();
+    let pinned_values: HashSet<Pips> = pinned.iter().copied().collect();
+    let pinned_sum: u32 = pinned.iter().map(|pip| pip.value() as u32).sum();
+
+    let mut choices = vec![ConstraintKind::Exactly];
+    if pinned_values.len() <= 1 {
+        choices.push(ConstraintKind::AllSame);
+    }
+    if free > 0 || pinned_sum < (points.len() as u32) * (Pips::MAX as u32) {
+        choices.push(ConstraintKind::LessThan);
+    }
+    if free > 0 || pinned_sum > 0 {
+        choices.push(ConstraintKind::MoreThan);
+    }
+    if points.len() > 1
+        && points.len() <= (Pips::MAX as usize + 1)
+        && pinned_values.len() == pinned.len()
+    {
         choices.push(ConstraintKind::AllDifferent);
     }
     let idx = rng.gen_range_usize(0, choices.len() - 1);
     let kind = choices[idx];
-    build_constraint(points, kind, rng)
+    build_constraint(points, kind, fixed_cells, rng)
 }
 
 #[derive(Clone, Copy)]
@@ -561,12 +622,23 @@ enum ConstraintKind {
 fn build_constraint(
     points: Vec<Point>,
     kind: ConstraintKind,
+    fixed_cells: &HashMap<Point, Pips>,
     rng: &mut SimpleRng,
 ) -> Result<(Constraint, Vec<(Point, Pips)>), String> {
     let points_set: Arc<HashSet<Point>> = Arc::new(points.iter().copied().collect());
+    let pinned: Vec<(Point, Pips)> = points
+        .iter()
+        .filter_map(|point| fixed_cells.get(point).map(|pip| (*point, *pip)))
+        .collect();
     match kind {
         ConstraintKind::AllSame => {
-            let value = random_pip(rng);
+            let value = match pinned.first() {
+                Some(&(_, value)) => value,
+                None => random_pip(rng),
+            };
+            if let Some(&(point, pip)) = pinned.iter().find(|(_, pip)| *pip != value) {
+                return Err(pin_conflict(point, pip, "AllSame"));
+            }
             let assignments: Vec<(Point, Pips)> = points.iter().map(|p| (*p, value)).collect();
             let constraint = Constraint::AllSame {
                 expected: Some(value),
@@ -575,15 +647,24 @@ fn build_constraint(
             Ok((constraint, assignments))
         }
         ConstraintKind::AllDifferent => {
+            let mut used = HashSet::new();
+            for &(point, pip) in &pinned {
+                if !used.insert(pip) {
+                    return Err(pin_conflict(point, pip, "AllDifferent"));
+                }
+            }
             let mut values: Vec<Pips> = (Pips::MIN..=Pips::MAX)
                 .map(|v| Pips::new(v).unwrap())
+                .filter(|pip| !used.contains(pip))
                 .collect();
             rng.shuffle(&mut values);
+            let mut values = values.into_iter();
             let assignments: Vec<(Point, Pips)> = points
                 .iter()
-                .zip(values.into_iter())
-                .take(points.len())
-                .map(|(p, pip)| (*p, pip))
+                .map(|p| match fixed_cells.get(p) {
+                    Some(pip) => (*p, *pip),
+                    None => (*p, values.next().expect("enough distinct pips")),
+                })
                 .collect();
             let constraint = Constraint::AllDifferent {
                 excluded: Arc::new(HashSet::new()),
@@ -592,7 +673,7 @@ fn build_constraint(
             Ok((constraint, assignments))
         }
         ConstraintKind::Exactly => {
-            let assignments = random_assignment(&points, rng);
+            let assignments = random_assignment(&points, fixed_cells, rng);
             let sum: u32 = assignments.iter().map(|(_, pip)| pip.value() as u32).sum();
             let constraint = Constraint::Exactly {
                 target: sum,
@@ -603,7 +684,7 @@ fn build_constraint(
         ConstraintKind::LessThan => {
             let max_sum = (points.len() as u32) * (Pips::MAX as u32);
             loop {
-                let sample = random_assignment(&points, rng);
+                let sample = random_assignment(&points, fixed_cells, rng);
                 let sum: u32 = sample.iter().map(|(_, pip)| pip.value() as u32).sum();
                 if sum < max_sum {
                     let remaining = max_sum - (sum + 1);
@@ -622,7 +703,7 @@ fn build_constraint(
             }
         }
         ConstraintKind::MoreThan => loop {
-            let sample = random_assignment(&points, rng);
+            let sample = random_assignment(&points, fixed_cells, rng);
             let sum: u32 = sample.iter().map(|(_, pip)| pip.value() as u32).sum();
             if sum > 0 {
                 let target = rng.gen_range_usize(0, (sum - 1) as usize) as u32;
@@ -636,13 +717,29 @@ fn build_constraint(
     }
 }
 
-fn random_assignment(points: &[Point], rng: &mut SimpleRng) -> Vec<(Point, Pips)> {
+fn random_assignment(
+    points: &[Point],
+    fixed_cells: &HashMap<Point, Pips>,
+    rng: &mut SimpleRng,
+) -> Vec<(Point, Pips)> {
     points
         .iter()
-        .map(|point| (*point, random_pip(rng)))
+        .map(|point| match fixed_cells.get(point) {
+            Some(pip) => (*point, *pip),
+            None => (*point, random_pip(rng)),
+        })
         .collect()
 }
 
+fn pin_conflict(point: Point, pip: Pips, kind: &str) -> String {
+    format!(
+        "Fixed cell {} = {} conflicts with the {} constraint covering it.",
+        point,
+        pip.value(),
+        kind
+    )
+}
+
 fn random_pip(rng: &mut SimpleRng) -> Pips {
     let value = rng.gen_range_inclusive(Pips::MIN, Pips::MAX);
     Pips::new(value).expect("range produces valid pip")
@@ -650,9 +747,21 @@ fn random_pip(rng: &mut SimpleRng) -> Pips {
 
 fn fill_remaining_cells(
     board_points: &HashSet<Point>,
+    fixed_cells: &HashMap<Point, Pips>,
     board_pips: &mut HashMap<Point, Pips>,
     rng: &mut SimpleRng,
 ) -> Result<(), String> {
+    for (point, pip) in fixed_cells {
+        let assigned = *board_pips.entry(*point).or_insert(*pip);
+        if assigned != *pip {
+            return Err(format!(
+                "Fixed cell {} = {} was assigned {} by a constraint.",
+                point,
+                pip.value(),
+                assigned.value()
+            ));
+        }
+    }
     for point in board_points {
         board_pips.entry(*point).or_insert_with(|| random_pip(rng));
     }
@@ -693,19 +802,117 @@ fn materialize_pieces(
     Ok((pieces, placements))
 }
 
-fn gcd_usize(a: usize, b: usize) -> usize {
-    if a == 0 {
-        return b;
-    }
-    if b == 0 {
-        return a;
-    }
-    let mut x = a;
-    let mut y = b;
-    while y != 0 {
-        let temp = y;
-        y = x % y;
-        x = temp;
+#[cfg(test)]
+mod tests {
+    use super::{ConstraintKind, TILING_BACKTRACKS, build_constraint, generate, tile_board};
+    use crate::model::{Board, Pips, Point, PolyShape, reduce_constraints};
+    use crate::polypips::config::{GeneratorConfig, TilingOrder};
+    use crate::polypips::rules::{ConstraintRule, ConstraintSelection, PieceRule};
+    use crate::util::rng::SimpleRng;
+    use std::collections::{HashMap, HashSet};
+
+    fn grid(width: u32, height: u32) -> Board {
+        let mut points = HashSet::new();
+        for y in 0..height {
+            for x in 0..width {
+                points.insert(Point::new(x, y));
+            }
+        }
+        Board::new(points)
+    }
+
+    #[test]
+    fn fixed_cells_keep_their_pinned_value() {
+        let pinned = Point::new(1, 1);
+        let five = Pips::new(5).unwrap();
+        let mut constrained_runs = 0;
+        for seed in 0..20 {
+            let config = GeneratorConfig {
+                board: grid(4, 3),
+                piece_rule: PieceRule::Unlimited(vec![PolyShape::Domino]),
+                constraint_rule: ConstraintRule::Allowed(vec![PolyShape::Mono, PolyShape::Domino]),
+                coverage: 0.5,
+                selection: ConstraintSelection::UniformAll,
+                seed: Some(seed),
+                fixed_cells: HashMap::from([(pinned, five)]),
+                tiling_order: TilingOrder::TopLeft,
+            };
+            let puzzle = generate(config).expect("puzzle generates");
+            let value = puzzle
+                .placements
+                .iter()
+                .find_map(|placement| placement.pip_at(pinned));
+            assert_eq!(value, Some(five), "seed {}", seed);
+
+            if puzzle
+                .constraints
+                .iter()
+                .any(|constraint| constraint.points().contains(&pinned))
+            {
+                constrained_runs += 1;
+            }
+            let mut constraints = puzzle.constraints.clone();
+            for placement in &puzzle.placements {
+                constraints = reduce_constraints(&constraints, placement).expect("consistent");
+            }
+            assert!(constraints.is_empty(), "seed {}", seed);
+        }
+        assert!(constrained_runs > 0, "pinned cell was never constrained");
+    }
+
+    #[test]
+    fn conflicting_pins_are_rejected() {
+        let points = vec![Point::new(0, 0), Point::new(1, 0)];
+        let fixed = HashMap::from([
+            (points[0], Pips::new(1).unwrap()),
+            (points[1], Pips::new(2).unwrap()),
+        ]);
+        let mut rng = SimpleRng::new(Some(1), 0, 0);
+        let result = build_constraint(points, ConstraintKind::AllSame, &fixed, &mut rng);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn fixed_cells_must_be_on_the_board() {
+        // Three cells cannot be tiled with dominoes, so this only passes if the pins are
+        // checked before the tiling search runs.
+        let config = GeneratorConfig {
+            board: grid(3, 1),
+            piece_rule: PieceRule::Unlimited(vec![PolyShape::Domino]),
+            constraint_rule: ConstraintRule::None,
+            coverage: 0.0,
+            selection: ConstraintSelection::UniformAll,
+            seed: Some(1),
+            fixed_cells: HashMap::from([(Point::new(5, 5), Pips::new(0).unwrap())]),
+            tiling_order: TilingOrder::TopLeft,
+        };
+        let err = generate(config).err().expect("pin is off the board");
+        assert!(err.contains("Fixed cell"), "{}", err);
+    }
+
+    #[test]
+    fn interior_first_backtracks_less_on_ring_board() {
+        let ring: HashSet<Point> = grid(8, 8)
+            .to_hash_set()
+            .into_iter()
+            .filter(|point| !(2..6).contains(&point.x) || !(2..6).contains(&point.y))
+            .collect();
+        let shapes = PieceRule::Unlimited(vec![PolyShape::TriL]);
+        let backtracks = |order: TilingOrder| {
+            TILING_BACKTRACKS.with(|count| count.set(0));
+            for seed in 0..20 {
+                let mut rng = SimpleRng::new(Some(seed), 8, 8);
+                tile_board(&ring, &shapes, order, &mut rng).expect("ring tiles");
+            }
+            TILING_BACKTRACKS.with(|count| count.get())
+        };
+        let top_left = backtracks(TilingOrder::TopLeft);
+        let interior_first = backtracks(TilingOrder::InteriorFirst);
+        assert!(
+            interior_first < top_left,
+            "{} >= {}",
+            interior_first,
+            top_left
+        );
     }
-    x
 }
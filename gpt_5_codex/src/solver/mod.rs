@@ -1,49 +1,661 @@
-use crate::model::{
-    Board, Constraint, Game, Piece, Placement, Point, reduce_constraints, remove_one,
-};
-use std::collections::HashMap;
+use crate::model::piece::remove_one;
+use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// The search `auto_solve` hands a puzzle to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SolverStrategy {
+    /// Exact-cover tiling first, pips assigned afterwards (`solver::solve`).
+    ExactCover,
+    /// MRV backtracking that reduces constraints as it places (`solver_v2::solve`).
+    Backtracking,
+}
+
+/// Picks the search expected to suit `game`.
+///
+/// Exact cover enumerates bare tilings and only checks constraints once a tiling is complete,
+/// so it is chosen when constraints cover less than half the board *and* either the pieces
+/// come in more than one shape (diverse shapes keep the tiling count low) or the board has
+/// at most 16 cells. Everything else goes to backtracking: constraint-heavy puzzles prune
+/// early there, and large boards of interchangeable pieces have too many tilings to list.
+pub fn select_strategy(game: &Game) -> SolverStrategy {
+    let cells = game.board.len();
+    let sparse = game.constraint_density() < 0.5;
+    let shapes: HashSet<_> = game.pieces.iter().map(|piece| piece.shape()).collect();
+    if sparse && (shapes.len() > 1 || cells <= 16) {
+        SolverStrategy::ExactCover
+    } else {
+        SolverStrategy::Backtracking
+    }
+}
+
+/// Solves `game` with whichever search `select_strategy` picks for it.
+pub fn auto_solve(game: &Game) -> Result<Vec<Placement>, String> {
+    match select_strategy(game) {
+        SolverStrategy::ExactCover => solve(game),
+        SolverStrategy::Backtracking => crate::solver_v2::solve(game),
+    }
+}
+
+/// Search effort for `game`: how many placements backtracking tries before it finds a
+/// solution. Larger counts mean more guessing, which tracks how hard a puzzle feels.
+pub fn estimate_difficulty(game: &Game) -> Result<usize, String> {
+    crate::solver_v2::count_search_nodes(game)
+}
+
+/// Most `estimate_difficulty` nodes a puzzle may take for `difficulty_label` to call it "easy"
+/// or "medium". Fitted to the NYT labels of the puzzles in `examples/`, about two thirds of
+/// which they reproduce; the rest mostly land one step off.
+pub const EASY_MAX_NODES: usize = 10;
+pub const MEDIUM_MAX_NODES: usize = 27;
+
+/// NYT-style difficulty from an `estimate_difficulty` node count: the more placements the
+/// search tries, the harder the label.
+pub fn difficulty_label(nodes: usize) -> &'static str {
+    if nodes <= EASY_MAX_NODES {
+        "easy"
+    } else if nodes <= MEDIUM_MAX_NODES {
+        "medium"
+    } else {
+        "hard"
+    }
+}
+
+/// Viable choices per search depth on the way to the first solution; see
+/// `solver_v2::branching_profile`. All ones means the puzzle is fully forced.
+pub fn branching_profile(game: &Game) -> Result<Vec<usize>, String> {
+    crate::solver_v2::branching_profile(game)
+}
+
+pub use crate::solver_v2::DecisionStep;
+
+/// Solves `game` with backtracking and returns the placements along with each decision on the
+/// winning path; see `solver_v2::solve_with_trace`.
+pub fn solve_with_trace(game: &Game) -> Result<(Vec<Placement>, Vec<DecisionStep>), String> {
+    crate::solver_v2::solve_with_trace(game)
+}
 
 pub fn solve(game: &Game) -> Result<Vec<Placement>, String> {
-    let pieces = game.pieces.clone();
-    let catalog = PlacementCatalog::new(&game.board, &pieces, &game.constraints);
-    let mut cover = ExactCover::new(&catalog);
-    let mut solution_rows = Vec::new();
-    let mut best: Option<Vec<Placement>> = None;
+    solve_with_filter(game, &allow_all)
+}
+
+/// Like `solve`, but gives up once `deadline` passes. `Ok(None)` means the search timed out;
+/// an exhausted search without a solution is still an error.
+pub fn solve_with_deadline(
+    game: &Game,
+    deadline: Instant,
+) -> Result<Option<Vec<Placement>>, String> {
+    solve_with_progress(game, deadline, &mut |_| {})
+}
+
+/// Search nodes explored between two `solve_with_progress` reports.
+pub const PROGRESS_INTERVAL: usize = 1 << 14;
 
-    cover.search(&mut solution_rows, &mut |rows| {
-        if let (Some(placements), _) = assign_pips(game, &catalog, rows, true) {
-            best = Some(placements);
+/// Like `solve_with_deadline`, but hands `on_progress` the number of search nodes explored so
+/// far every `PROGRESS_INTERVAL` nodes, and the final count once the search ends.
+pub fn solve_with_progress(
+    game: &Game,
+    deadline: Instant,
+    on_progress: &mut dyn FnMut(usize),
+) -> Result<Option<Vec<Placement>>, String> {
+    solve_limited(
+        game,
+        SearchLimits {
+            deadline: Some(deadline),
+            progress: Some(on_progress),
+            ..SearchLimits::default()
+        },
+    )
+}
+
+/// Like `solve`, but gives up with `Ok(None)` after exploring `max_nodes` search nodes.
+pub fn solve_with_node_limit(
+    game: &Game,
+    max_nodes: usize,
+) -> Result<Option<Vec<Placement>>, String> {
+    solve_limited(
+        game,
+        SearchLimits {
+            max_nodes: Some(max_nodes),
+            ..SearchLimits::default()
+        },
+    )
+}
+
+/// Like `solve`, but gives up with `Ok(None)` once another thread sets `cancel`. The flag is
+/// checked at every tiling search node and every pip-assignment step.
+pub fn solve_cancellable(
+    game: &Game,
+    cancel: Arc<AtomicBool>,
+) -> Result<Option<Vec<Placement>>, String> {
+    solve_limited(
+        game,
+        SearchLimits {
+            cancel: Some(&cancel),
+            ..SearchLimits::default()
+        },
+    )
+}
+
+fn solve_limited(game: &Game, limits: SearchLimits) -> Result<Option<Vec<Placement>>, String> {
+    let mut solution = None;
+    let stopped = search_solutions_limited(
+        game,
+        &allow_all,
+        &SolveOptions::default(),
+        limits,
+        &mut |placements| {
+            solution = Some(placements.to_vec());
             true
-        } else {
-            false
+        },
+    );
+    match solution {
+        Some(mut placements) => {
+            canonicalize_placements(&mut placements);
+            Ok(Some(placements))
         }
+        None if stopped => Ok(None),
+        None => Err("No valid placements.".to_string()),
+    }
+}
+
+/// Solves `game` and returns the pip that ends up on each board cell.
+pub fn solve_assignment(game: &Game) -> Result<HashMap<Point, Pips>, String> {
+    solve(game).map(|placements| assignment_map(&placements))
+}
+
+fn assignment_map(placements: &[Placement]) -> HashMap<Point, Pips> {
+    placements
+        .iter()
+        .flat_map(|placement| placement.assignments())
+        .map(|assignment| (assignment.point, assignment.pips))
+        .collect()
+}
+
+/// Decides whether a candidate placement (piece, anchor, orientation and pip order) may
+/// appear in a solution. Any `Fn(&Placement) -> bool` closure works as a filter.
+pub trait PlacementFilter {
+    fn allows(&self, placement: &Placement) -> bool;
+}
+
+impl<F> PlacementFilter for F
+where
+    F: Fn(&Placement) -> bool,
+{
+    fn allows(&self, placement: &Placement) -> bool {
+        self(placement)
+    }
+}
+
+fn allow_all(_: &Placement) -> bool {
+    true
+}
+
+/// Extra rules for `solve_with_options`.
+#[derive(Clone, Debug, Default)]
+pub struct SolveOptions {
+    /// Most times any one piece (same shape and pips) may be placed in a solution.
+    pub max_reuse: Option<usize>,
+    /// Treat identical pieces as interchangeable, so solutions that only swap identical
+    /// pieces between their spots are found once.
+    pub interchangeable_pieces: bool,
+    /// Let pieces go unplaced; the board still has to be covered exactly. See `solve_subset`.
+    pub allow_unused_pieces: bool,
+}
+
+/// Like `solve`, but honours `options` during the search.
+pub fn solve_with_options(game: &Game, options: &SolveOptions) -> Result<Vec<Placement>, String> {
+    let mut solution = None;
+    search_solutions(game, &allow_all, options, None, &mut |placements| {
+        solution = Some(placements.to_vec());
+        true
+    });
+    let mut placements = solution.ok_or_else(|| "No valid placements.".to_string())?;
+    canonicalize_placements(&mut placements);
+    Ok(placements)
+}
+
+/// Like `solve`, but tiles the board with any subset of the pieces, so decoy pieces may be
+/// left over. Returns the placements together with the pieces that went unused.
+pub fn solve_subset(game: &Game) -> Result<(Vec<Placement>, Vec<Piece>), String> {
+    let options = SolveOptions {
+        allow_unused_pieces: true,
+        ..SolveOptions::default()
+    };
+    let placements = solve_with_options(game, &options)?;
+    let mut unused = game.pieces.clone();
+    for placement in &placements {
+        unused = remove_one(unused, &placement.piece)?;
+    }
+    Ok((placements, unused))
+}
+
+/// Like `solve`, but never uses a placement rejected by `filter`. Rejected placements are
+/// dropped while building the catalog, so they cost nothing during the search.
+pub fn solve_with_filter(
+    game: &Game,
+    filter: &dyn PlacementFilter,
+) -> Result<Vec<Placement>, String> {
+    let mut solution = None;
+    search_solutions(
+        game,
+        filter,
+        &SolveOptions::default(),
+        None,
+        &mut |placements| {
+            solution = Some(placements.to_vec());
+            true
+        },
+    );
+    let mut placements = solution.ok_or_else(|| "No valid placements.".to_string())?;
+    canonicalize_placements(&mut placements);
+    Ok(placements)
+}
+
+/// Like `solve`, but every cell in `prefill` must end up with the given pips, whichever piece
+/// covers it. Placements whose pip order contradicts a prefilled cell never enter the search.
+pub fn solve_with_prefill(
+    game: &Game,
+    prefill: &HashMap<Point, Pips>,
+) -> Result<Vec<Placement>, String> {
+    if let Some(point) = prefill
+        .keys()
+        .find(|point| !game.board.contains_point(point))
+    {
+        return Err(format!("Prefilled cell {} is not on the board.", point));
+    }
+    solve_with_filter(game, &|placement: &Placement| {
+        placement.assignments().iter().all(|assignment| {
+            prefill
+                .get(&assignment.point)
+                .is_none_or(|&expected| expected == assignment.pips)
+        })
+    })
+}
+
+/// Sorts placements by anchor (row-major), then shape code, then pip order, so a solution
+/// reads the same regardless of how the input listed its pieces.
+pub fn canonicalize_placements(placements: &mut [Placement]) {
+    placements.sort_by_cached_key(|placement| {
+        let pips: Vec<Pips> = placement
+            .assignments()
+            .iter()
+            .map(|assignment| assignment.pips)
+            .collect();
+        (
+            placement.anchor.y,
+            placement.anchor.x,
+            placement.piece.shape().code(),
+            pips,
+            placement.orientation_index,
+        )
     });
+}
+
+/// Collects up to `limit` distinct solutions, stopping the search as soon as that many are found.
+pub fn find_solutions(game: &Game, limit: usize) -> Result<Vec<Vec<Placement>>, String> {
+    let mut solutions = Vec::new();
+    if limit == 0 {
+        return Ok(solutions);
+    }
+    search_solutions(
+        game,
+        &allow_all,
+        &SolveOptions::default(),
+        None,
+        &mut |placements| {
+            solutions.push(placements.to_vec());
+            solutions.len() >= limit
+        },
+    );
+    Ok(solutions)
+}
+
+/// Finds the solution whose pips disagree with `target` on the fewest cells. Cells missing
+/// from `target` are free. Every solution is scored as the search yields it, keeping the best
+/// so far, and the search stops early once an exact match (distance 0) turns up.
+pub fn solve_closest(game: &Game, target: &HashMap<Point, Pips>) -> Result<Vec<Placement>, String> {
+    let mut best: Option<(usize, Vec<Placement>)> = None;
+    search_solutions(
+        game,
+        &allow_all,
+        &SolveOptions::default(),
+        None,
+        &mut |placements| {
+            let distance = solution_distance(placements, target);
+            if best.as_ref().is_none_or(|(current, _)| distance < *current) {
+                best = Some((distance, placements.to_vec()));
+            }
+            distance == 0
+        },
+    );
+    best.map(|(_, placements)| placements)
+        .ok_or_else(|| "No valid placements.".to_string())
+}
+
+/// Among all solutions, the one whose tightest `LessThan`/`MoreThan` bound has the most slack
+/// (see `Constraint::slack`). Ties keep the first solution found; without any such bounds
+/// every solution ties, so this is the first one.
+pub fn solve_max_slack(game: &Game) -> Result<Vec<Placement>, String> {
+    let mut best: Option<(i64, Vec<Placement>)> = None;
+    search_solutions(
+        game,
+        &allow_all,
+        &SolveOptions::default(),
+        None,
+        &mut |placements| {
+            let slack = solution_slack(game, placements);
+            if best.as_ref().is_none_or(|(current, _)| slack > *current) {
+                best = Some((slack, placements.to_vec()));
+            }
+            false
+        },
+    );
+    best.map(|(_, placements)| placements)
+        .ok_or_else(|| "No valid placements.".to_string())
+}
+
+fn solution_slack(game: &Game, placements: &[Placement]) -> i64 {
+    let assignment = assignment_map(placements);
+    game.constraints
+        .iter()
+        .filter_map(|constraint| constraint.slack(&assignment))
+        .min()
+        .unwrap_or(i64::MAX)
+}
+
+fn solution_distance(placements: &[Placement], target: &HashMap<Point, Pips>) -> usize {
+    placements
+        .iter()
+        .flat_map(|placement| placement.assignments())
+        .filter(|assignment| {
+            target
+                .get(&assignment.point)
+                .is_some_and(|&expected| expected != assignment.pips)
+        })
+        .count()
+}
+
+/// Where two solutions disagree: cells whose pips differ (with each side's pip, `None` if
+/// that side leaves the cell uncovered) and placements that only one side uses.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SolutionDiff {
+    pub cells: Vec<(Point, Option<Pips>, Option<Pips>)>,
+    pub only_in_a: Vec<Placement>,
+    pub only_in_b: Vec<Placement>,
+}
+
+impl SolutionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty() && self.only_in_a.is_empty() && self.only_in_b.is_empty()
+    }
+}
+
+impl fmt::Display for SolutionDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |pips: Option<Pips>| pips.map_or("-".to_string(), |pips| pips.to_string());
+        for (point, a, b) in &self.cells {
+            writeln!(f, "{}: {} -> {}", point, show(*a), show(*b))?;
+        }
+        for placement in &self.only_in_a {
+            writeln!(f, "- {}", placement)?;
+        }
+        for placement in &self.only_in_b {
+            writeln!(f, "+ {}", placement)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two solutions cell by cell, e.g. the intended solution against what a player
+/// found. Cells are listed in row-major order.
+pub fn diff_solutions(a: &[Placement], b: &[Placement]) -> SolutionDiff {
+    let pips_a = assignment_map(a);
+    let pips_b = assignment_map(b);
+    let mut points: Vec<Point> = pips_a.keys().chain(pips_b.keys()).copied().collect();
+    points.sort_by_key(|point| (point.y, point.x));
+    points.dedup();
+    let cells = points
+        .into_iter()
+        .filter_map(|point| {
+            let (left, right) = (pips_a.get(&point).copied(), pips_b.get(&point).copied());
+            (left != right).then_some((point, left, right))
+        })
+        .collect();
+    let missing_from = |side: &[Placement], other: &[Placement]| -> Vec<Placement> {
+        side.iter()
+            .filter(|placement| !other.contains(placement))
+            .cloned()
+            .collect()
+    };
+    SolutionDiff {
+        cells,
+        only_in_a: missing_from(a, b),
+        only_in_b: missing_from(b, a),
+    }
+}
 
-    best.ok_or_else(|| "No valid placements.".to_string())
+/// Saves `placements` as JSON so a solution can be replayed against its game file later
+/// (see `read_solution`) instead of being solved again.
+pub fn write_solution<P: AsRef<Path>>(path: P, placements: &[Placement]) -> Result<(), String> {
+    let path = path.as_ref();
+    let json = serde_json::to_string_pretty(placements)
+        .map_err(|err| format!("Failed to encode solution: {}", err))?;
+    fs::write(path, json).map_err(|err| format!("Failed to write {}: {}", path.display(), err))
+}
+
+/// Loads placements saved by `write_solution`. They are not checked against any game; replay
+/// them with `Game::apply_placements` to verify.
+pub fn read_solution<P: AsRef<Path>>(path: P) -> Result<Vec<Placement>, String> {
+    let path = path.as_ref();
+    let json = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+    serde_json::from_str(&json)
+        .map_err(|err| format!("Failed to parse solution {}: {}", path.display(), err))
 }
 
 pub fn count_solutions(game: &Game) -> Result<usize, String> {
-    let pieces = game.pieces.clone();
-    let catalog = PlacementCatalog::new(&game.board, &pieces, &game.constraints);
-    let mut cover = ExactCover::new(&catalog);
-    let mut solution_rows = Vec::new();
-    let mut total = 0usize;
+    count_solutions_with_options(game, &SolveOptions::default())
+}
 
-    cover.search(&mut solution_rows, &mut |rows| {
-        let (_, count) = assign_pips(game, &catalog, rows, false);
-        total += count;
+/// Like `count_solutions`, but honours `options` during the search.
+pub fn count_solutions_with_options(game: &Game, options: &SolveOptions) -> Result<usize, String> {
+    let mut total = 0usize;
+    search_solutions(game, &allow_all, options, None, &mut |_| {
+        total += 1;
         false
     });
-
     Ok(total)
 }
 
-fn assign_pips(
+/// Number of candidate placements (one per piece, orientation and anchor) covering each board
+/// cell before anything is placed.
+pub(crate) fn candidate_counts(game: &Game) -> HashMap<Point, usize> {
+    let catalog = PlacementCatalog::new(&game.board, &game.pieces, &game.constraints, &allow_all);
+    let points: Vec<Point> = game.board.iter().collect();
+    let mut counts: HashMap<Point, usize> = points.iter().map(|&point| (point, 0)).collect();
+    for entry in &catalog.entries {
+        for &column in &entry.cell_columns {
+            *counts.get_mut(&points[column]).unwrap() += 1;
+        }
+    }
+    counts
+}
+
+/// Feeds every complete solution to `on_solution`; the search stops once it returns `true`
+/// or `deadline` passes. Returns whether the deadline cut the search short.
+fn search_solutions<F>(
+    game: &Game,
+    filter: &dyn PlacementFilter,
+    options: &SolveOptions,
+    deadline: Option<Instant>,
+    on_solution: &mut F,
+) -> bool
+where
+    F: FnMut(&[Placement]) -> bool,
+{
+    let limits = SearchLimits {
+        deadline,
+        ..SearchLimits::default()
+    };
+    search_solutions_limited(game, filter, options, limits, on_solution)
+}
+
+/// Ways to stop or observe a search from outside, beyond `on_solution` asking it to stop.
+#[derive(Default)]
+struct SearchLimits<'a> {
+    deadline: Option<Instant>,
+    cancel: Option<&'a AtomicBool>,
+    max_nodes: Option<usize>,
+    progress: Option<&'a mut dyn FnMut(usize)>,
+}
+
+impl<'a> SearchLimits<'a> {
+    fn interrupt(&self) -> Interrupt<'a> {
+        Interrupt {
+            deadline: self.deadline,
+            cancel: self.cancel,
+            fired: Cell::new(false),
+        }
+    }
+}
+
+/// The deadline and cancel flag of a `SearchLimits`, checked by pip assignment between tiling
+/// search nodes; remembers whether either fired.
+struct Interrupt<'a> {
+    deadline: Option<Instant>,
+    cancel: Option<&'a AtomicBool>,
+    fired: Cell<bool>,
+}
+
+impl Interrupt<'_> {
+    fn check(&self) -> bool {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+            || self
+                .cancel
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            self.fired.set(true);
+        }
+        self.fired.get()
+    }
+}
+
+/// Like `search_solutions`, but returns whether the deadline or cancellation cut the search
+/// short.
+fn search_solutions_limited<F>(
+    game: &Game,
+    filter: &dyn PlacementFilter,
+    options: &SolveOptions,
+    limits: SearchLimits,
+    on_solution: &mut F,
+) -> bool
+where
+    F: FnMut(&[Placement]) -> bool,
+{
+    let pieces = game.pieces.clone();
+    let catalog = PlacementCatalog::new(&game.board, &pieces, &game.constraints, filter);
+    let mut cover = ExactCover::new(&catalog, options.interchangeable_pieces);
+    cover.max_reuse = options.max_reuse;
+    if options.allow_unused_pieces {
+        cover.primary_columns = catalog.board_cell_count;
+    }
+    let interrupt = limits.interrupt();
+    cover.limits = limits;
+    let mut solution_rows = Vec::new();
+
+    if game.constraints.is_empty() {
+        cover.search(&mut solution_rows, &mut |rows| {
+            emit_unconstrained(&catalog, rows, &interrupt, on_solution)
+        });
+    } else {
+        cover.search(&mut solution_rows, &mut |rows| {
+            assign_pips(game, &catalog, rows, &interrupt, on_solution)
+        });
+    }
+    let nodes = cover.nodes;
+    if let Some(progress) = cover.limits.progress.as_mut() {
+        progress(nodes);
+    }
+    cover.stopped || interrupt.fired.get()
+}
+
+/// Without constraints every tiling is a solution under any pip order, so the pip
+/// arrangements are enumerated directly, natural order first, with no constraint replay.
+fn emit_unconstrained<F>(
+    catalog: &PlacementCatalog,
+    rows: &[usize],
+    interrupt: &Interrupt,
+    on_solution: &mut F,
+) -> bool
+where
+    F: FnMut(&[Placement]) -> bool,
+{
+    let entries: Vec<&PlacementEntry> = rows.iter().map(|&idx| &catalog.entries[idx]).collect();
+    let mut choice = vec![0usize; entries.len()];
+    loop {
+        if interrupt.check() {
+            return true;
+        }
+        let placements: Vec<Placement> = entries
+            .iter()
+            .zip(&choice)
+            .map(|(entry, &pick)| {
+                Placement::new(
+                    entry.piece.clone(),
+                    entry.anchor,
+                    entry.orientation_index,
+                    entry.pip_orders[pick].clone(),
+                )
+            })
+            .collect();
+        if on_solution(&placements) {
+            return true;
+        }
+
+        // Advance the mixed-radix counter over each entry's pip arrangements.
+        let mut position = 0;
+        loop {
+            if position == entries.len() {
+                return false;
+            }
+            choice[position] += 1;
+            if choice[position] < entries[position].pip_orders.len() {
+                break;
+            }
+            choice[position] = 0;
+            position += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static ASSIGN_PIPS_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+fn assign_pips<F>(
     game: &Game,
     catalog: &PlacementCatalog,
     rows: &[usize],
-    stop_at_first: bool,
-) -> (Option<Vec<Placement>>, usize) {
+    interrupt: &Interrupt,
+    on_solution: &mut F,
+) -> bool
+where
+    F: FnMut(&[Placement]) -> bool,
+{
+    #[cfg(test)]
+    ASSIGN_PIPS_CALLS.with(|calls| calls.set(calls.get() + 1));
+
     let mut entries: Vec<&PlacementEntry> = rows.iter().map(|&idx| &catalog.entries[idx]).collect();
     entries.sort_by(|a, b| {
         b.constraint_score
@@ -53,39 +665,29 @@ fn assign_pips(
     });
 
     let mut placements = Vec::with_capacity(entries.len());
-    let mut best: Option<Vec<Placement>> = None;
-    let mut count = 0usize;
-    assign_pips_recursive(
-        game,
-        &entries,
-        0,
-        &mut placements,
-        &mut best,
-        &mut count,
-        stop_at_first,
-    );
-    (best, count)
+    assign_pips_recursive(game, &entries, 0, &mut placements, interrupt, on_solution)
 }
 
-fn assign_pips_recursive(
+fn assign_pips_recursive<F>(
     state: &Game,
     entries: &[&PlacementEntry],
     index: usize,
     placements: &mut Vec<Placement>,
-    best: &mut Option<Vec<Placement>>,
-    count: &mut usize,
-    stop_at_first: bool,
-) -> bool {
+    interrupt: &Interrupt,
+    on_solution: &mut F,
+) -> bool
+where
+    F: FnMut(&[Placement]) -> bool,
+{
+    if interrupt.check() {
+        return true;
+    }
     if index == entries.len() {
-        *count += 1;
-        if best.is_none() {
-            *best = Some(placements.clone());
-        }
-        return stop_at_first;
+        return on_solution(placements);
     }
 
     let entry = entries[index];
-    for pip_order in entry.piece.pip_permutations() {
+    for pip_order in &entry.pip_orders {
         let placement = Placement::new(
             entry.piece.clone(),
             entry.anchor,
@@ -100,9 +702,8 @@ fn assign_pips_recursive(
                     entries,
                     index + 1,
                     placements,
-                    best,
-                    count,
-                    stop_at_first,
+                    interrupt,
+                    on_solution,
                 ) {
                     return true;
                 }
@@ -120,15 +721,7 @@ fn assign_pips_recursive(
 }
 
 fn play(game: &Game, placement: &Placement) -> Result<Game, String> {
-    let placement_points = placement.points();
-    let board_result = game.board.remove_points(&placement_points);
-    let pieces_result = remove_one(game.pieces.clone(), &placement.piece);
-    let constraints_result = reduce_constraints(&game.constraints, placement);
-
-    match (board_result, pieces_result, constraints_result) {
-        (Ok(board), Ok(pieces), Ok(constraints)) => Ok(Game::new(board, pieces, constraints)),
-        _ => Err("Unwinnable game.".to_string()),
-    }
+    game.apply_placements(std::slice::from_ref(placement))
 }
 
 struct PlacementEntry {
@@ -137,6 +730,7 @@ struct PlacementEntry {
     piece_shape_order: usize,
     anchor: Point,
     orientation_index: usize,
+    pip_orders: Vec<Vec<Pips>>,
     cell_columns: Vec<usize>,
     constraint_score: usize,
 }
@@ -145,20 +739,39 @@ struct PlacementCatalog {
     entries: Vec<PlacementEntry>,
     board_cell_count: usize,
     piece_count: usize,
+    /// Distinct-piece id of each piece; identical pieces share an id.
+    piece_kinds: Vec<usize>,
 }
 
 impl PlacementCatalog {
-    fn new(board: &Board, pieces: &[Piece], constraints: &[Constraint]) -> Self {
+    fn new(
+        board: &Board,
+        pieces: &[Piece],
+        constraints: &[Constraint],
+        filter: &dyn PlacementFilter,
+    ) -> Self {
         let mut index_map = HashMap::new();
-        for (idx, point) in board.iter().enumerate() {
+        let mut dense_index = vec![usize::MAX; board.total_cells()];
+        for (idx, (bit, point)) in board.iter_indexed().enumerate() {
             index_map.insert(point, idx);
+            dense_index[bit] = idx;
         }
 
+        let mut kinds: HashMap<&Piece, usize> = HashMap::new();
+        let piece_kinds = pieces
+            .iter()
+            .map(|piece| {
+                let next_kind = kinds.len();
+                *kinds.entry(piece).or_insert(next_kind)
+            })
+            .collect();
+
         if index_map.is_empty() {
             return Self {
                 entries: Vec::new(),
                 board_cell_count: 0,
                 piece_count: pieces.len(),
+                piece_kinds,
             };
         }
 
@@ -168,30 +781,28 @@ impl PlacementCatalog {
             let piece_shape_order = piece.shape().cell_count();
             for (orientation_index, offsets) in piece.orientations().iter().enumerate() {
                 for anchor in board.iter() {
-                    let mut cell_columns = Vec::with_capacity(offsets.len());
-                    let mut valid = true;
-                    for &(dx, dy) in offsets {
-                        let x = anchor.x as i32 + dx;
-                        let y = anchor.y as i32 + dy;
-                        if x < 0 || y < 0 {
-                            valid = false;
-                            break;
-                        }
-                        let point = Point::new(x as u32, y as u32);
-                        if let Some(&index) = index_map.get(&point) {
-                            cell_columns.push(index);
-                        } else {
-                            valid = false;
-                            break;
-                        }
-                    }
+                    let Some(bits) = board.placement_indices(anchor, offsets) else {
+                        continue;
+                    };
+                    let cell_columns: Vec<usize> =
+                        bits.iter().map(|&bit| dense_index[bit]).collect();
 
-                    if !valid {
+                    let pip_orders: Vec<Vec<Pips>> = piece
+                        .pip_permutations()
+                        .into_iter()
+                        .filter(|pip_order| {
+                            filter.allows(&Placement::new(
+                                piece.clone(),
+                                anchor,
+                                orientation_index,
+                                pip_order.clone(),
+                            ))
+                        })
+                        .collect();
+                    if pip_orders.is_empty() {
                         continue;
                     }
 
-                    cell_columns.sort_unstable();
-
                     let mut constraint_score = 0usize;
                     for constraint in constraints {
                         if constraint.points().iter().any(|p| {
@@ -210,6 +821,7 @@ impl PlacementCatalog {
                         piece_shape_order,
                         anchor,
                         orientation_index,
+                        pip_orders,
                         cell_columns: cell_columns.clone(),
                         constraint_score,
                     });
@@ -221,29 +833,70 @@ impl PlacementCatalog {
             entries,
             board_cell_count: index_map.len(),
             piece_count: pieces.len(),
+            piece_kinds,
         }
     }
 }
 
-struct ExactCover {
+struct ExactCover<'a> {
     column_rows: Vec<Vec<usize>>,
     row_columns: Vec<Vec<usize>>,
     active_columns: Vec<bool>,
     active_rows: Vec<bool>,
     column_size: Vec<usize>,
+    /// How many more chosen rows each column needs: 1 for cells and distinct pieces, the
+    /// multiplicity for a merged piece-type column.
+    column_remaining: Vec<usize>,
+    /// Columns before this index must be covered; the rest (piece columns, when pieces may
+    /// go unused) are covered at most once.
+    primary_columns: usize,
+    limits: SearchLimits<'a>,
+    stopped: bool,
+    /// Distinct-piece id of each row; identical pieces share an id.
+    row_kind: Vec<usize>,
+    kind_uses: Vec<usize>,
+    max_reuse: Option<usize>,
+    nodes: usize,
 }
 
-impl ExactCover {
-    fn new(catalog: &PlacementCatalog) -> Self {
+impl<'a> ExactCover<'a> {
+    /// With `merge_identical`, identical pieces share one piece-type column that must be
+    /// covered once per copy, and only the first copy's rows take part in the search.
+    fn new(catalog: &PlacementCatalog, merge_identical: bool) -> Self {
         let row_count = catalog.entries.len();
-        let column_count = catalog.board_cell_count + catalog.piece_count;
+        let kind_count = catalog.piece_kinds.iter().max().map_or(0, |kind| kind + 1);
+        let mut column_remaining = vec![1; catalog.board_cell_count];
+        if merge_identical {
+            let mut multiplicity = vec![0; kind_count];
+            for &kind in &catalog.piece_kinds {
+                multiplicity[kind] += 1;
+            }
+            column_remaining.extend(multiplicity);
+        } else {
+            column_remaining.extend(vec![1; catalog.piece_count]);
+        }
+        let column_count = column_remaining.len();
 
         let mut row_columns = Vec::with_capacity(row_count);
         let mut column_rows: Vec<Vec<usize>> = vec![Vec::new(); column_count];
+        let mut row_kind = Vec::with_capacity(row_count);
+        let mut active_rows = vec![true; row_count];
 
         for (row_index, entry) in catalog.entries.iter().enumerate() {
+            let kind = catalog.piece_kinds[entry.piece_index];
+            row_kind.push(kind);
+            let piece_column = if merge_identical {
+                if catalog.piece_kinds[..entry.piece_index].contains(&kind) {
+                    active_rows[row_index] = false;
+                    row_columns.push(Vec::new());
+                    continue;
+                }
+                kind
+            } else {
+                entry.piece_index
+            };
             let mut columns = entry.cell_columns.clone();
-            columns.push(catalog.board_cell_count + entry.piece_index);
+            columns.push(catalog.board_cell_count + piece_column);
             columns.sort_unstable();
             for &column in &columns {
                 column_rows[column].push(row_index);
@@ -253,7 +906,6 @@ impl ExactCover {
 
         let column_size = column_rows.iter().map(|rows| rows.len()).collect();
         let active_columns = vec![true; column_count];
-        let active_rows = vec![true; row_count];
 
         Self {
             column_rows,
@@ -261,6 +913,14 @@ impl ExactCover {
             active_columns,
             active_rows,
             column_size,
+            column_remaining,
+            primary_columns: column_count,
+            limits: SearchLimits::default(),
+            stopped: false,
+            row_kind,
+            kind_uses: vec![0; kind_count],
+            max_reuse: None,
+            nodes: 0,
         }
     }
 
@@ -268,6 +928,27 @@ impl ExactCover {
     where
         F: FnMut(&[usize]) -> bool,
     {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(PROGRESS_INTERVAL) {
+            let nodes = self.nodes;
+            if let Some(progress) = self.limits.progress.as_mut() {
+                progress(nodes);
+            }
+        }
+        if self
+            .limits
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+            || self
+                .limits
+                .cancel
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            || self.limits.max_nodes.is_some_and(|cap| self.nodes > cap)
+        {
+            self.stopped = true;
+            return true;
+        }
+
         let column = match self.select_column() {
             Some(index) => index,
             None => {
@@ -280,16 +961,31 @@ impl ExactCover {
         }
 
         let state = self.cover_column(column);
-        let rows = self.column_rows[column].clone();
+        // Only rows still active before covering are compatible with the partial solution.
+        let rows: Vec<usize> = state
+            .rows_removed
+            .iter()
+            .map(|removal| removal.row)
+            .collect();
         for row in rows {
+            let kind = self.row_kind[row];
+            if self
+                .max_reuse
+                .is_some_and(|cap| self.kind_uses[kind] >= cap)
+            {
+                continue;
+            }
+            self.kind_uses[kind] += 1;
             solution.push(row);
             let mut row_states = Vec::new();
             let row_columns = self.row_columns[row].clone();
-            for col in row_columns {
+            for &col in &row_columns {
+                self.column_remaining[col] -= 1;
                 if col == column {
                     continue;
                 }
-                if self.active_columns[col] {
+                // A piece-type column with copies left stays open for the next copy.
+                if self.active_columns[col] && self.column_remaining[col] == 0 {
                     row_states.push(self.cover_column(col));
                 }
             }
@@ -301,7 +997,11 @@ impl ExactCover {
             for state in row_states.into_iter().rev() {
                 self.uncover_column(state);
             }
+            for &col in &row_columns {
+                self.column_remaining[col] += 1;
+            }
             solution.pop();
+            self.kind_uses[kind] -= 1;
         }
 
         self.uncover_column(state);
@@ -311,7 +1011,12 @@ impl ExactCover {
     fn select_column(&self) -> Option<usize> {
         let mut best: Option<usize> = None;
         let mut best_size = usize::MAX;
-        for (index, active) in self.active_columns.iter().enumerate() {
+        for (index, active) in self
+            .active_columns
+            .iter()
+            .enumerate()
+            .take(self.primary_columns)
+        {
             if !*active {
                 continue;
             }
@@ -319,6 +1024,11 @@ impl ExactCover {
             if size == 0 {
                 return Some(index);
             }
+            // Branching on a column that still needs several rows would find the same
+            // copies in every order; the cell columns decide where they go instead.
+            if self.column_remaining[index] > 1 {
+                continue;
+            }
             if size < best_size {
                 best_size = size;
                 best = Some(index);
@@ -385,10 +1095,54 @@ struct RowRemoval {
 
 #[cfg(test)]
 mod tests {
-    use super::{count_solutions, solve};
-    use crate::model::{Board, Constraint, Game, Piece, Pips, Point, PolyShape};
-    use std::collections::HashSet;
+    use super::{
+        ASSIGN_PIPS_CALLS, EASY_MAX_NODES, MEDIUM_MAX_NODES, SearchLimits, SolveOptions,
+        SolverStrategy, allow_all, assignment_map, auto_solve, branching_profile,
+        canonicalize_placements, count_solutions, count_solutions_with_options, diff_solutions,
+        difficulty_label, estimate_difficulty, find_solutions, play, read_solution,
+        search_solutions_limited, select_strategy, solution_distance, solve, solve_assignment,
+        solve_cancellable, solve_closest, solve_max_slack, solve_subset, solve_with_deadline,
+        solve_with_filter, solve_with_node_limit, solve_with_options, solve_with_prefill,
+        solve_with_progress, solve_with_trace, write_solution,
+    };
+    use crate::loader;
+    use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point, PolyShape};
+    use std::collections::{HashMap, HashSet};
+    use std::path::Path;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    fn load_fixture(path: &str) -> Game {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path);
+        loader::load_game_from_path(path).expect("load fixture")
+    }
+
+    fn assert_solves(game: &Game, expected: SolverStrategy) {
+        assert_eq!(select_strategy(game), expected);
+        let placements = auto_solve(game).expect("auto_solve finds a solution");
+        let finished = placements
+            .iter()
+            .try_fold(game.clone(), |state, placement| play(&state, placement))
+            .expect("every placement is legal");
+        assert!(finished.is_won());
+    }
+
+    #[test]
+    fn auto_solve_handles_domino_dense_fixture() {
+        assert_solves(
+            &load_fixture("poly_games/4x4.txt"),
+            SolverStrategy::ExactCover,
+        );
+    }
+
+    #[test]
+    fn auto_solve_handles_constraint_dense_fixture() {
+        assert_solves(
+            &load_fixture("examples/game-2025-08-18-easy.txt"),
+            SolverStrategy::Backtracking,
+        );
+    }
 
     #[test]
     fn solves_single_piece_board() {
@@ -416,6 +1170,32 @@ mod tests {
         assert_eq!(total, 1);
     }
 
+    #[test]
+    fn find_solutions_stops_at_limit() {
+        let mut points = HashSet::new();
+        for y in 0..2 {
+            for x in 0..4 {
+                points.insert(Point::new(x, y));
+            }
+        }
+        let pieces = (1..=4)
+            .map(|value| Piece::domino(Pips::new(value).unwrap(), Pips::new(value).unwrap()))
+            .collect();
+        let game = Game::new(Board::new(points), pieces, vec![]);
+        assert!(count_solutions(&game).unwrap() > 3);
+
+        let solutions = find_solutions(&game, 3).expect("search succeeds");
+        assert_eq!(solutions.len(), 3);
+        for (idx, solution) in solutions.iter().enumerate() {
+            let finished = solution
+                .iter()
+                .try_fold(game.clone(), |state, placement| play(&state, placement))
+                .expect("every placement is legal");
+            assert!(finished.is_won());
+            assert!(!solutions[..idx].contains(solution));
+        }
+    }
+
     #[test]
     fn counts_zero_for_unsolved_game() {
         let mut points = HashSet::new();
@@ -458,4 +1238,480 @@ mod tests {
         let solution = solve(&game).expect("solution should exist");
         assert_eq!(solution.len(), 1);
     }
+
+    #[test]
+    fn filter_keeps_banned_placements_out_of_the_solution() {
+        let mut points = HashSet::new();
+        for y in 0..2 {
+            for x in 0..2 {
+                points.insert(Point::new(x, y));
+            }
+        }
+        let pieces = vec![
+            Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+        ];
+        let game = Game::new(Board::new(points), pieces, vec![]);
+
+        let is_horizontal = |placement: &Placement| {
+            let cells = placement.points();
+            cells[0].y == cells[1].y
+        };
+        let solution = solve_with_filter(&game, &|placement: &Placement| !is_horizontal(placement))
+            .expect("vertical tiling exists");
+        assert_eq!(solution.len(), 2);
+        assert!(solution.iter().all(|placement| !is_horizontal(placement)));
+
+        let banned_everything = solve_with_filter(&game, &|_: &Placement| false);
+        assert!(banned_everything.is_err());
+    }
+
+    #[test]
+    fn solve_closest_prefers_matching_solution() {
+        let mut points = HashSet::new();
+        for y in 0..2 {
+            for x in 0..2 {
+                points.insert(Point::new(x, y));
+            }
+        }
+        let pieces = vec![
+            Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+        ];
+        let game = Game::new(Board::new(points), pieces, vec![]);
+
+        let solutions = find_solutions(&game, 5).expect("search succeeds");
+        let wanted = solutions.last().expect("several solutions exist");
+        let target = assignment_map(wanted);
+        let closest = solve_closest(&game, &target).expect("closest solution");
+        assert_eq!(solution_distance(&closest, &target), 0);
+        assert_eq!(assignment_map(&closest), target);
+
+        let mut perturbed = target.clone();
+        let (&point, &pips) = perturbed.iter().next().unwrap();
+        let other = Pips::new((pips.value() + 1) % 7).unwrap();
+        perturbed.insert(point, other);
+        let closest = solve_closest(&game, &perturbed).expect("closest solution");
+        assert!(solution_distance(&closest, &perturbed) <= 1);
+    }
+
+    #[test]
+    fn solve_with_trace_records_one_step_per_placement() {
+        let game = load_fixture("examples/game-2025-08-18-easy.txt");
+        let (placements, steps) = solve_with_trace(&game).expect("solvable");
+        assert_eq!(steps.len(), placements.len());
+        for (step, placement) in steps.iter().zip(&placements) {
+            assert_eq!(&step.placement, placement);
+            assert!(step.placement.points().contains(&step.pivot));
+        }
+    }
+
+    #[test]
+    fn solve_with_node_limit_gives_up_at_the_cap() {
+        let game = load_fixture("poly_games/4x4.txt");
+        assert_eq!(solve_with_node_limit(&game, 0), Ok(None));
+        assert!(solve_with_node_limit(&game, usize::MAX).unwrap().is_some());
+    }
+
+    #[test]
+    fn solve_cancellable_stops_once_flag_is_set() {
+        let game = load_fixture("poly_games/8x8_minus4.txt");
+        let started = Instant::now();
+        let cancelled = solve_cancellable(&game, Arc::new(AtomicBool::new(true)));
+        assert_eq!(cancelled, Ok(None));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let small = load_fixture("poly_games/2x2.txt");
+        let solved = solve_cancellable(&small, Arc::new(AtomicBool::new(false)));
+        assert!(solved.expect("search succeeds").is_some());
+    }
+
+    #[test]
+    fn solve_with_progress_reports_final_node_count() {
+        let game = load_fixture("poly_games/4x4.txt");
+        let far = Instant::now() + Duration::from_secs(60);
+        let mut reports = Vec::new();
+        let solution = solve_with_progress(&game, far, &mut |nodes| reports.push(nodes))
+            .expect("search succeeds");
+        assert!(solution.is_some());
+        assert!(reports.last().is_some_and(|&nodes| nodes > 0));
+        assert!(reports.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn solve_max_slack_keeps_bounds_away_from_their_limits() {
+        let left = Point::new(0, 0);
+        let right = Point::new(1, 0);
+        let board = Board::new([left, right].into_iter().collect());
+        let pieces = vec![Piece::domino(Pips::new(1).unwrap(), Pips::new(5).unwrap())];
+        let constraints = vec![
+            Constraint::LessThan {
+                target: 6,
+                points: Arc::new([left].into_iter().collect()),
+            },
+            Constraint::MoreThan {
+                target: 0,
+                points: Arc::new([right].into_iter().collect()),
+            },
+        ];
+        let game = Game::new(board, pieces, constraints);
+        game.validate().unwrap();
+        assert_eq!(find_solutions(&game, 5).expect("search succeeds").len(), 2);
+
+        let assignment = assignment_map(&solve_max_slack(&game).expect("solvable"));
+        assert_eq!(assignment[&left], Pips::new(1).unwrap());
+        assert_eq!(assignment[&right], Pips::new(5).unwrap());
+    }
+
+    #[test]
+    fn solve_assignment_maps_every_cell() {
+        let game = load_fixture("poly_games/2x2.txt");
+        let assignment = solve_assignment(&game).expect("solvable");
+        assert_eq!(assignment.len(), game.board.len());
+        assert!(
+            game.board
+                .iter()
+                .all(|point| assignment.contains_key(&point))
+        );
+
+        let placements = solve(&game).expect("solvable");
+        for placement in &placements {
+            for cell in placement.assignments() {
+                assert_eq!(assignment.get(&cell.point), Some(&cell.pips));
+            }
+        }
+    }
+
+    #[test]
+    fn solve_with_deadline_distinguishes_timeout_from_success() {
+        let game = load_fixture("poly_games/2x2.txt");
+        let far = Instant::now() + Duration::from_secs(60);
+        assert!(solve_with_deadline(&game, far).unwrap().is_some());
+
+        let past = Instant::now();
+        assert_eq!(solve_with_deadline(&game, past), Ok(None));
+    }
+
+    #[test]
+    fn unconstrained_games_skip_pip_assignment() {
+        let mut points = HashSet::new();
+        for y in 0..2 {
+            for x in 0..3 {
+                points.insert(Point::new(x, y));
+            }
+        }
+        let pieces = vec![
+            Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+            Piece::domino(Pips::new(5).unwrap(), Pips::new(6).unwrap()),
+        ];
+        let game = Game::new(Board::new(points), pieces, vec![]);
+
+        ASSIGN_PIPS_CALLS.with(|calls| calls.set(0));
+        let solution = solve(&game).expect("tiling exists");
+        let finished = solution
+            .iter()
+            .try_fold(game.clone(), |state, placement| play(&state, placement))
+            .expect("every placement is legal");
+        assert!(finished.is_won());
+        // Three tilings of a 3x2 board, 3! ways to deal the pieces, 2^3 pip orders.
+        assert_eq!(count_solutions(&game).unwrap(), 144);
+        assert_eq!(ASSIGN_PIPS_CALLS.with(|calls| calls.get()), 0);
+    }
+
+    #[test]
+    fn solutions_are_canonical_regardless_of_piece_order() {
+        let mut points = HashSet::new();
+        let mut constraints = Vec::new();
+        for (x, y, value) in [(0, 0, 1), (1, 0, 2), (0, 1, 3), (1, 1, 4)] {
+            let point = Point::new(x, y);
+            points.insert(point);
+            constraints.push(Constraint::Exactly {
+                target: value,
+                points: Arc::new([point].into_iter().collect()),
+            });
+        }
+        let board = Board::new(points);
+        let low = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let high = Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap());
+
+        let forward = Game::new(
+            board.clone(),
+            vec![low.clone(), high.clone()],
+            constraints.clone(),
+        );
+        let reversed = Game::new(board, vec![high, low], constraints);
+        let first = solve(&forward).expect("solvable");
+        let second = solve(&reversed).expect("solvable");
+        assert_eq!(first, second);
+        assert_eq!(first[0].anchor, Point::new(0, 0));
+
+        let mut shuffled = vec![first[1].clone(), first[0].clone()];
+        canonicalize_placements(&mut shuffled);
+        assert_eq!(shuffled, first);
+    }
+
+    #[test]
+    fn max_reuse_caps_identical_pieces() {
+        let mut points = HashSet::new();
+        for y in 0..2 {
+            for x in 0..3 {
+                points.insert(Point::new(x, y));
+            }
+        }
+        let board = Board::new(points);
+        let double = Piece::domino(Pips::new(2).unwrap(), Pips::new(2).unwrap());
+        let other = Piece::domino(Pips::new(1).unwrap(), Pips::new(5).unwrap());
+        let capped = SolveOptions {
+            max_reuse: Some(2),
+            ..SolveOptions::default()
+        };
+
+        let three_doubles = Game::new(board.clone(), vec![double.clone(); 3], vec![]);
+        assert!(solve_with_options(&three_doubles, &capped).is_err());
+        assert!(solve_with_options(&three_doubles, &SolveOptions::default()).is_ok());
+
+        let two_doubles = Game::new(board, vec![double.clone(), other, double], vec![]);
+        let solution = solve_with_options(&two_doubles, &capped).expect("within the cap");
+        assert_eq!(solution.len(), 3);
+    }
+
+    #[test]
+    fn diff_solutions_lists_cells_that_differ() {
+        let points: HashSet<Point> = (0..2)
+            .flat_map(|y| (0..2).map(move |x| Point::new(x, y)))
+            .collect();
+        let top = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let bottom = Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap());
+        let game = Game::new(
+            Board::new(points),
+            vec![top.clone(), bottom.clone()],
+            vec![],
+        );
+        let pips = |values: [u8; 2]| values.map(|value| Pips::new(value).unwrap()).to_vec();
+        let shared = Placement::new(top, Point::new(0, 0), 0, pips([1, 2]));
+        let intended = vec![
+            shared.clone(),
+            Placement::new(bottom.clone(), Point::new(0, 1), 0, pips([3, 4])),
+        ];
+        let found = vec![
+            shared,
+            Placement::new(bottom, Point::new(0, 1), 0, pips([4, 3])),
+        ];
+        for solution in [&intended, &found] {
+            assert!(game.apply_placements(solution).unwrap().is_won());
+        }
+
+        assert!(diff_solutions(&intended, &intended).is_empty());
+        let diff = diff_solutions(&intended, &found);
+        assert_eq!(
+            diff.cells,
+            vec![
+                (
+                    Point::new(0, 1),
+                    Some(Pips::new(3).unwrap()),
+                    Some(Pips::new(4).unwrap())
+                ),
+                (
+                    Point::new(1, 1),
+                    Some(Pips::new(4).unwrap()),
+                    Some(Pips::new(3).unwrap())
+                ),
+            ]
+        );
+        assert_eq!(diff.only_in_a, vec![intended[1].clone()]);
+        assert_eq!(diff.only_in_b, vec![found[1].clone()]);
+    }
+
+    #[test]
+    fn prefill_forces_domino_orientation() {
+        let points: HashSet<Point> = [Point::new(0, 0), Point::new(1, 0)].into_iter().collect();
+        let piece = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let game = Game::new(Board::new(points), vec![piece], vec![]);
+
+        for value in [1, 2] {
+            let prefill = HashMap::from([(Point::new(0, 0), Pips::new(value).unwrap())]);
+            let solution = solve_with_prefill(&game, &prefill).expect("prefill is consistent");
+            assert_eq!(
+                solution[0].pip_at(Point::new(0, 0)),
+                Some(Pips::new(value).unwrap())
+            );
+        }
+
+        let inconsistent = HashMap::from([(Point::new(1, 0), Pips::new(5).unwrap())]);
+        assert!(solve_with_prefill(&game, &inconsistent).is_err());
+        let off_board = HashMap::from([(Point::new(3, 3), Pips::new(1).unwrap())]);
+        let err = solve_with_prefill(&game, &off_board).unwrap_err();
+        assert!(err.contains("(3, 3)"), "{}", err);
+    }
+
+    #[test]
+    fn branching_profile_is_all_ones_when_forced() {
+        let cells: Vec<Point> = (0..4).map(|x| Point::new(x, 0)).collect();
+        let board = Board::new(cells.iter().copied().collect());
+        let pieces = vec![
+            Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+        ];
+        let constraints = cells
+            .iter()
+            .zip(1..)
+            .map(|(&point, target)| Constraint::Exactly {
+                target,
+                points: Arc::new(HashSet::from([point])),
+            })
+            .collect();
+        let game = Game::new(board.clone(), pieces.clone(), constraints);
+        assert_eq!(branching_profile(&game).unwrap(), vec![1, 1]);
+
+        let open = Game::new(board, pieces, vec![]);
+        let profile = branching_profile(&open).unwrap();
+        assert_eq!(profile.len(), 2);
+        assert!(profile[0] > 1, "{:?}", profile);
+    }
+
+    #[test]
+    fn written_solution_reads_back_and_replays() {
+        let game = load_fixture("poly_games/3x3.txt");
+        let solution = solve(&game).expect("fixture solves");
+        let path = std::env::temp_dir().join(format!(
+            "pips_solution_{}_{:?}.json",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        write_solution(&path, &solution).expect("write solution");
+        let restored = read_solution(&path);
+        std::fs::remove_file(&path).ok();
+
+        let restored = restored.expect("read solution");
+        assert_eq!(restored, solution);
+        assert!(game.apply_placements(&restored).unwrap().is_won());
+    }
+
+    #[test]
+    fn interchangeable_pieces_count_each_tiling_once() {
+        let points: HashSet<Point> = (0..2)
+            .flat_map(|y| (0..2).map(move |x| Point::new(x, y)))
+            .collect();
+        let double = Piece::domino(Pips::new(3).unwrap(), Pips::new(3).unwrap());
+        let game = Game::new(Board::new(points), vec![double.clone(), double], vec![]);
+        let merged = SolveOptions {
+            interchangeable_pieces: true,
+            ..SolveOptions::default()
+        };
+
+        let naive = count_solutions(&game).unwrap();
+        let distinct = count_solutions_with_options(&game, &merged).unwrap();
+        assert_eq!(naive, 4);
+        assert_eq!(distinct * 2, naive);
+    }
+
+    #[test]
+    fn solve_subset_leaves_the_decoy_unused() {
+        let points: HashSet<Point> = (0..4).map(|x| Point::new(x, 0)).collect();
+        let row = Arc::new(points.clone());
+        let (low, high, decoy) = (
+            Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+            Piece::domino(Pips::new(6).unwrap(), Pips::new(6).unwrap()),
+        );
+        let game = Game::new(
+            Board::new(points),
+            vec![low, decoy.clone(), high],
+            vec![Constraint::Exactly {
+                target: 10,
+                points: row,
+            }],
+        );
+        assert!(solve(&game).is_err());
+
+        let (placements, unused) = solve_subset(&game).expect("a subset tiles the board");
+        assert_eq!(unused, vec![decoy]);
+        let covered: HashSet<Point> = placements
+            .iter()
+            .flat_map(|placement| placement.assignments())
+            .map(|assignment| assignment.point)
+            .collect();
+        assert_eq!(covered.len(), 4);
+        assert_eq!(placements.len(), 2);
+    }
+
+    #[test]
+    fn cancel_set_during_pip_assignment_stops_it() {
+        let points: HashSet<Point> = [Point::new(0, 0), Point::new(1, 0)].into_iter().collect();
+        let piece = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let loose = Game::new(Board::new(points.clone()), vec![piece.clone()], vec![]);
+        let constrained = Game::new(
+            Board::new(points.clone()),
+            vec![piece],
+            vec![Constraint::LessThan {
+                target: 20,
+                points: Arc::new(points),
+            }],
+        );
+
+        // One tiling with two pip orders, so both solutions come out of the same pip
+        // assignment call; cancelling from the first must stop it before the second.
+        for game in [loose, constrained] {
+            assert_eq!(count_solutions(&game).unwrap(), 2);
+            let cancel = AtomicBool::new(false);
+            let limits = SearchLimits {
+                cancel: Some(&cancel),
+                ..SearchLimits::default()
+            };
+            let mut seen = 0;
+            let stopped = search_solutions_limited(
+                &game,
+                &allow_all,
+                &SolveOptions::default(),
+                limits,
+                &mut |_| {
+                    seen += 1;
+                    cancel.store(true, Ordering::Relaxed);
+                    false
+                },
+            );
+            assert!(stopped);
+            assert_eq!(seen, 1);
+        }
+    }
+
+    #[test]
+    fn difficulty_label_splits_at_the_node_thresholds() {
+        assert_eq!(difficulty_label(EASY_MAX_NODES), "easy");
+        assert_eq!(difficulty_label(EASY_MAX_NODES + 1), "medium");
+        assert_eq!(difficulty_label(MEDIUM_MAX_NODES), "medium");
+        assert_eq!(difficulty_label(MEDIUM_MAX_NODES + 1), "hard");
+    }
+
+    #[test]
+    fn difficulty_label_mostly_agrees_with_nyt_examples() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("../examples");
+        let mut total = 0;
+        let mut agreed = 0;
+        for entry in std::fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let Some(stem) = name
+                .strip_prefix("game-")
+                .and_then(|rest| rest.strip_suffix(".txt"))
+            else {
+                continue;
+            };
+            let nyt = stem.rsplit('-').next().unwrap();
+            let game = loader::load_game_from_path(&path).unwrap();
+            let nodes = estimate_difficulty(&game).unwrap();
+            total += 1;
+            if difficulty_label(nodes) == nyt {
+                agreed += 1;
+            }
+        }
+        assert!(total > 0);
+        assert!(
+            agreed * 100 >= total * 65,
+            "{} of {} labels agree",
+            agreed,
+            total
+        );
+    }
 }
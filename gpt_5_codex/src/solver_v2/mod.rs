@@ -1,37 +1,203 @@
-use crate::model::{Board, Constraint, Game, Piece, Placement, Point, reduce_constraints};
+use crate::model::{
+    Board, Constraint, Game, Piece, Pips, Placement, Point, reduce_constraints, remove_one,
+};
+use crate::util::math::gcd;
+use crate::util::rng::SimpleRng;
 use std::collections::{HashMap, HashSet};
 use std::mem;
 
+/// Extra search strategies for `solve_with`.
+#[derive(Clone, Debug, Default)]
+pub struct SearchOptions {
+    /// Solve one connected board component at a time, in `Game::partition_components` order,
+    /// instead of interleaving placements across components.
+    pub sequential_components: bool,
+}
+
 pub fn solve(game: &Game) -> Result<Vec<Placement>, String> {
+    solve_with(game, &SearchOptions::default())
+}
+
+pub fn solve_with(game: &Game, options: &SearchOptions) -> Result<Vec<Placement>, String> {
+    solve_counting(game, options, &mut 0)
+}
+
+/// `solve_with`, adding the placements every search it runs applies to `nodes`.
+fn solve_counting(
+    game: &Game,
+    options: &SearchOptions,
+    nodes: &mut usize,
+) -> Result<Vec<Placement>, String> {
+    if options.sequential_components {
+        solve_components(game, nodes)
+    } else {
+        solve_joint(game, nodes)
+    }
+}
+
+fn solve_joint(game: &Game, nodes: &mut usize) -> Result<Vec<Placement>, String> {
     let catalog = PlacementCatalog::from_game(game)?;
-    let mut remaining = vec![true; catalog.board_points.len()];
-    let mut used_pieces = vec![false; catalog.piece_count];
-    let mut constraints = game.constraints.clone();
-    let mut placements: Vec<Placement> = Vec::with_capacity(game.pieces.len());
-    let mut cells_remaining = catalog.board_points.len();
-
-    if search(
-        game,
-        &catalog,
-        &mut remaining,
-        &mut used_pieces,
-        &mut constraints,
-        &mut placements,
-        &mut cells_remaining,
-    ) {
-        validate_solution(game, &placements)?;
-        Ok(placements)
+    let mut state = SearchState::new(game, &catalog);
+
+    let solved = search(game, &catalog, &mut state);
+    *nodes += state.nodes;
+    if solved {
+        validate_solution(game, &state.placements)?;
+        Ok(state.placements)
     } else {
         Err("No tiling found.".to_string())
     }
 }
 
+/// Solves each component with the pieces earlier components left over. A component that fails
+/// even with every piece available can never be solved, so that failure is final; any other
+/// failure only means earlier components took pieces it needed, and the whole board is then
+/// searched jointly, as is a board whose constraints cannot be partitioned by component.
+fn solve_components(game: &Game, nodes: &mut usize) -> Result<Vec<Placement>, String> {
+    let components = match game.partition_components() {
+        Ok(components) if components.len() >= 2 => components,
+        _ => return solve_joint(game, nodes),
+    };
+
+    let mut pool = game.pieces.clone();
+    let mut placements = Vec::with_capacity(game.pieces.len());
+    for (board, constraints) in components {
+        let with_pool = Game::new(board.clone(), pool.clone(), constraints.clone());
+        match solve_pool(&with_pool, nodes) {
+            Some(solution) => {
+                for placement in &solution {
+                    pool = remove_one(pool, &placement.piece)?;
+                }
+                placements.extend(solution);
+            }
+            None => {
+                let with_all = Game::new(board.clone(), game.pieces.clone(), constraints);
+                if solve_pool(&with_all, nodes).is_none() {
+                    let corner = board
+                        .iter()
+                        .min_by_key(|point| (point.y, point.x))
+                        .expect("components are non-empty");
+                    return Err(format!(
+                        "No tiling found: the board component containing {} ({} cells) cannot be solved with any of the pieces.",
+                        corner,
+                        board.len()
+                    ));
+                }
+                return solve_joint(game, nodes);
+            }
+        }
+    }
+    validate_solution(game, &placements)?;
+    Ok(placements)
+}
+
+/// Covers `game`'s board from its piece pool, which may hold more pieces than the board needs.
+fn solve_pool(game: &Game, nodes: &mut usize) -> Option<Vec<Placement>> {
+    let catalog = PlacementCatalog::from_game(game).ok()?;
+    let mut state = SearchState::new(game, &catalog);
+    let solved = search(game, &catalog, &mut state);
+    *nodes += state.nodes;
+    solved.then_some(state.placements)
+}
+
+/// Number of placements the search applies before it solves `game` or exhausts the tree.
+pub fn count_search_nodes(game: &Game) -> Result<usize, String> {
+    let catalog = PlacementCatalog::from_game(game)?;
+    let mut state = SearchState::new(game, &catalog);
+    search(game, &catalog, &mut state);
+    Ok(state.nodes)
+}
+
+/// How many viable placements the search had to choose from at each depth on the way to the
+/// first solution. A puzzle that never needs a guess profiles as all ones.
+pub fn branching_profile(game: &Game) -> Result<Vec<usize>, String> {
+    let catalog = PlacementCatalog::from_game(game)?;
+    let mut state = SearchState::new(game, &catalog);
+    if !search(game, &catalog, &mut state) {
+        return Err("No tiling found.".to_string());
+    }
+
+    let mut profile = Vec::with_capacity(state.undo_log.len());
+    replay_solution(game, &catalog, &state, |pivot, replay, _| {
+        let candidates = &catalog.cell_to_entries[pivot];
+        profile.push(
+            candidates
+                .iter()
+                .filter(|&&candidate| try_candidate(game, &catalog, replay, candidate).is_some())
+                .count(),
+        );
+    })?;
+    Ok(profile)
+}
+
+/// One decision on the path to a solution: the pivot cell the search branched on and the
+/// placement it committed there.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecisionStep {
+    pub pivot: Point,
+    pub placement: Placement,
+}
+
+/// Solves `game` and also returns the decisions along the winning path, in the order the
+/// search made them.
+pub fn solve_with_trace(game: &Game) -> Result<(Vec<Placement>, Vec<DecisionStep>), String> {
+    let catalog = PlacementCatalog::from_game(game)?;
+    let mut state = SearchState::new(game, &catalog);
+    if !search(game, &catalog, &mut state) {
+        return Err("No tiling found.".to_string());
+    }
+    validate_solution(game, &state.placements)?;
+
+    let mut steps = Vec::with_capacity(state.undo_log.len());
+    replay_solution(game, &catalog, &state, |pivot, _, placement| {
+        steps.push(DecisionStep {
+            pivot: catalog.board_points[pivot],
+            placement: placement.clone(),
+        });
+    })?;
+    Ok((state.placements, steps))
+}
+
+/// Replays the rows of a solved `state` from the start, showing `visit` each pivot cell, the
+/// state the search saw there, and the placement it chose.
+fn replay_solution<F>(
+    game: &Game,
+    catalog: &PlacementCatalog,
+    state: &SearchState,
+    mut visit: F,
+) -> Result<(), String>
+where
+    F: FnMut(usize, &SearchState, &Placement),
+{
+    // The pivot choice is deterministic, so replaying the solution's rows from the start
+    // revisits the same cells with the same remaining state the search saw.
+    let path: Vec<usize> = state.undo_log.iter().map(|undo| undo.entry_index).collect();
+    let mut replay = SearchState::new(game, catalog);
+    for entry_index in path {
+        let pivot = select_cell(catalog, &replay.remaining, &replay.used_pieces)
+            .ok_or_else(|| "Solution path could not be replayed.".to_string())?;
+        let (placement, next_constraints, next_ids) =
+            try_candidate(game, catalog, &replay, entry_index)
+                .ok_or_else(|| "Solution path could not be replayed.".to_string())?;
+        visit(pivot, &replay, &placement);
+        replay.apply(
+            entry_index,
+            &catalog.entries[entry_index],
+            placement,
+            next_constraints,
+            next_ids,
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 struct PlacementRow {
     piece_index: usize,
     orientation_index: usize,
     anchor: Point,
     cell_indices: Vec<usize>,
+    pip_order: Vec<Pips>,
 }
 
 struct PlacementCatalog {
@@ -43,10 +209,27 @@ struct PlacementCatalog {
 
 impl PlacementCatalog {
     fn from_game(game: &Game) -> Result<Self, String> {
-        let (index_map, board_points) = board_index_map(&game.board);
+        let (dense_index, board_points) = board_index_map(&game.board);
         if board_points.is_empty() {
             return Err("Board has no cells.".to_string());
         }
+        let size_gcd = game
+            .pieces
+            .iter()
+            .map(|piece| piece.shape().cell_count())
+            .fold(0, gcd);
+        if size_gcd == 0 {
+            return Err("No pieces to tile the board with.".to_string());
+        }
+        if board_points.len() % size_gcd != 0 {
+            return Err(format!(
+                "Board has {} cells, but every piece size is a multiple of {}, so no tiling exists.",
+                board_points.len(),
+                size_gcd
+            ));
+        }
+        check_all_different_supply(game)?;
+        check_all_same_supply(game)?;
 
         let mut entries = Vec::new();
         for (piece_index, piece) in game.pieces.iter().enumerate() {
@@ -54,7 +237,7 @@ impl PlacementCatalog {
                 piece_index,
                 piece,
                 &game.board,
-                &index_map,
+                &dense_index,
             ));
         }
 
@@ -74,124 +257,488 @@ impl PlacementCatalog {
     }
 }
 
-fn board_index_map(board: &Board) -> (HashMap<Point, usize>, Vec<Point>) {
-    let mut map = HashMap::new();
+/// Fails fast when an `AllDifferent` region has more cells than the piece pool has distinct
+/// usable pip values, which the search would otherwise only discover deep in the tree.
+fn check_all_different_supply(game: &Game) -> Result<(), String> {
+    let available: HashSet<Pips> = game
+        .pieces
+        .iter()
+        .flat_map(|piece| piece.pips().iter().copied())
+        .collect();
+    for constraint in &game.constraints {
+        if let Constraint::AllDifferent { excluded, points } = constraint {
+            let distinct = available.difference(excluded).count();
+            if distinct < points.len() {
+                return Err(format!(
+                    "AllDifferent region of {} cells needs {} distinct pip values, but the pieces only offer {}.",
+                    points.len(),
+                    points.len(),
+                    distinct
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fails fast when an `AllSame Some(v)` region has more cells than the piece pool has pips
+/// of value `v`.
+fn check_all_same_supply(game: &Game) -> Result<(), String> {
+    for constraint in &game.constraints {
+        if let Constraint::AllSame {
+            expected: Some(value),
+            points,
+        } = constraint
+        {
+            let available = game
+                .pieces
+                .iter()
+                .flat_map(|piece| piece.pips().iter())
+                .filter(|pips| *pips == value)
+                .count();
+            if available < points.len() {
+                return Err(format!(
+                    "AllSame {} region of {} cells needs {} pips of value {}, but the pieces only offer {}.",
+                    value,
+                    points.len(),
+                    points.len(),
+                    value,
+                    available
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Maps each board cell's bitset index to its position in `board.iter()` order, alongside the
+/// cells in that order.
+fn board_index_map(board: &Board) -> (Vec<usize>, Vec<Point>) {
+    let mut dense_index = vec![usize::MAX; board.total_cells()];
     let mut points = Vec::new();
-    for (idx, point) in board.iter().enumerate() {
-        map.insert(point, idx);
+    for (idx, (bit, point)) in board.iter_indexed().enumerate() {
+        dense_index[bit] = idx;
         points.push(point);
     }
-    (map, points)
+    (dense_index, points)
 }
 
 fn enumerate_piece_rows(
     piece_index: usize,
     piece: &Piece,
     board: &Board,
-    index_map: &HashMap<Point, usize>,
+    dense_index: &[usize],
 ) -> Vec<PlacementRow> {
     let mut rows = Vec::new();
+    let arrangements = piece.pip_permutations();
     for (orientation_index, offsets) in piece.orientations().iter().enumerate() {
         for anchor in board.iter() {
-            let mut cell_indices = Vec::with_capacity(offsets.len());
-            let mut valid = true;
-            for &(dx, dy) in offsets {
-                let x = anchor.x as i32 + dx;
-                let y = anchor.y as i32 + dy;
-                if x < 0 || y < 0 {
-                    valid = false;
-                    break;
-                }
-                let point = Point::new(x as u32, y as u32);
-                if let Some(&index) = index_map.get(&point) {
-                    cell_indices.push(index);
-                } else {
-                    valid = false;
-                    break;
-                }
-            }
-            if !valid {
+            let Some(bits) = board.placement_indices(anchor, offsets) else {
                 continue;
+            };
+            // Bitset order and `iter` order agree, so the dense indices stay sorted.
+            let cell_indices: Vec<usize> = bits.iter().map(|&bit| dense_index[bit]).collect();
+            for pip_order in &arrangements {
+                rows.push(PlacementRow {
+                    piece_index,
+                    orientation_index,
+                    anchor,
+                    cell_indices: cell_indices.clone(),
+                    pip_order: pip_order.clone(),
+                });
             }
-            cell_indices.sort_unstable();
-            rows.push(PlacementRow {
-                piece_index,
-                orientation_index,
-                anchor,
-                cell_indices,
-            });
         }
     }
     rows
 }
 
-fn search(
-    game: &Game,
-    catalog: &PlacementCatalog,
-    remaining: &mut [bool],
-    used_pieces: &mut [bool],
-    constraints: &mut Vec<Constraint>,
-    placements: &mut Vec<Placement>,
-    cells_remaining: &mut usize,
-) -> bool {
-    if *cells_remaining == 0 {
-        return constraints.is_empty();
+/// Zobrist keys: one random 64-bit value per board cell, per copy of each distinct piece, and
+/// per original constraint. The search XORs a cell's key out of its running hash when the
+/// cell is covered and the `n`th copy's key in when a piece is used for the `n`th time, so
+/// identical pieces hash alike. Live constraints contribute their residual state (see
+/// `residual_key`).
+struct ZobristKeys {
+    cells: Vec<u64>,
+    /// `pieces[kind][n]` marks the `n`th used copy of a distinct piece.
+    pieces: Vec<Vec<u64>>,
+    /// Distinct-piece id of each piece; identical pieces share an id.
+    piece_kinds: Vec<usize>,
+    constraints: Vec<u64>,
+}
+
+impl ZobristKeys {
+    fn new(game: &Game, cell_count: usize) -> Self {
+        let mut rng = SimpleRng::new(Some(0x5a0b_7157_c0de_2391), 0, 0);
+        let mut kinds: HashMap<&Piece, usize> = HashMap::new();
+        let piece_kinds: Vec<usize> = game
+            .pieces
+            .iter()
+            .map(|piece| {
+                let next_kind = kinds.len();
+                *kinds.entry(piece).or_insert(next_kind)
+            })
+            .collect();
+        let mut copies = vec![0; kinds.len()];
+        for &kind in &piece_kinds {
+            copies[kind] += 1;
+        }
+        Self {
+            cells: (0..cell_count).map(|_| rng.next_u64()).collect(),
+            pieces: copies
+                .into_iter()
+                .map(|count| (0..count).map(|_| rng.next_u64()).collect())
+                .collect(),
+            piece_kinds,
+            constraints: (0..game.constraints.len())
+                .map(|_| rng.next_u64())
+                .collect(),
+        }
+    }
+
+    /// Key for constraint `id` in its current, possibly reduced, form. Its open points follow
+    /// from the open cells, so the kind and what it still demands are enough.
+    fn residual_key(&self, id: usize, constraint: &Constraint) -> u64 {
+        let (kind, demand) = match constraint {
+            Constraint::AllSame { expected, .. } => {
+                (0, expected.map_or(0, |pips| pips.value_u32() + 1))
+            }
+            Constraint::AllDifferent { excluded, .. } => (
+                1,
+                excluded
+                    .iter()
+                    .fold(0, |mask, pips| mask | 1 << pips.value()),
+            ),
+            Constraint::Exactly { target, .. } => (2, *target),
+            Constraint::LessThan { target, .. } => (3, *target),
+            Constraint::MoreThan { target, .. } => (4, *target),
+        };
+        let mixed = (self.constraints[id] ^ ((kind as u64) << 32 | demand as u64))
+            .wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        mixed ^ mixed >> 29
+    }
+
+    fn constraints_hash(&self, ids: &[usize], constraints: &[Constraint]) -> u64 {
+        ids.iter()
+            .zip(constraints)
+            .fold(0, |hash, (&id, constraint)| {
+                hash ^ self.residual_key(id, constraint)
+            })
     }
+}
+
+/// Mutable search state shared by every frame of the explicit search stack.
+struct SearchState {
+    remaining: Vec<bool>,
+    used_pieces: Vec<bool>,
+    constraints: Vec<Constraint>,
+    /// Index into the game's original constraints for each entry of `constraints`.
+    constraint_ids: Vec<usize>,
+    placements: Vec<Placement>,
+    cells_remaining: usize,
+    /// How many pips of each value the unused pieces still carry.
+    pip_counts: PipCounts,
+    undo_log: Vec<UndoEntry>,
+    keys: ZobristKeys,
+    /// Uses so far of each distinct piece, indexing `keys.pieces`.
+    kind_uses: Vec<usize>,
+    hash: u64,
+    constraint_hash: u64,
+    nodes: usize,
+}
 
-    let pivot = match select_cell(catalog, remaining, used_pieces) {
-        Some(cell) => cell,
+impl SearchState {
+    fn new(game: &Game, catalog: &PlacementCatalog) -> Self {
+        let keys = ZobristKeys::new(game, catalog.board_points.len());
+        let ids: Vec<usize> = (0..game.constraints.len()).collect();
+        let constraint_hash = keys.constraints_hash(&ids, &game.constraints);
+        let hash = keys
+            .cells
+            .iter()
+            .fold(constraint_hash, |hash, key| hash ^ key);
+        Self {
+            remaining: vec![true; catalog.board_points.len()],
+            used_pieces: vec![false; catalog.piece_count],
+            constraints: game.constraints.clone(),
+            constraint_ids: ids,
+            placements: Vec::with_capacity(game.pieces.len()),
+            cells_remaining: catalog.board_points.len(),
+            pip_counts: pip_counts(&game.pieces),
+            undo_log: Vec::with_capacity(game.pieces.len()),
+            kind_uses: vec![0; keys.pieces.len()],
+            keys,
+            hash,
+            constraint_hash,
+            nodes: 0,
+        }
+    }
+
+    /// Running Zobrist hash of the open cells, the multiset of used pieces and the residual
+    /// constraints, for a transposition cache to key on.
+    #[allow(dead_code)]
+    pub(crate) fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn apply(
+        &mut self,
+        entry_index: usize,
+        entry: &PlacementRow,
+        placement: Placement,
+        next: Vec<Constraint>,
+        next_ids: Vec<usize>,
+    ) {
+        let previous_hash = self.hash;
+        let previous_constraint_hash = self.constraint_hash;
+        for &cell in &entry.cell_indices {
+            self.remaining[cell] = false;
+            self.hash ^= self.keys.cells[cell];
+        }
+        self.used_pieces[entry.piece_index] = true;
+        for pips in placement.piece.pips() {
+            self.pip_counts[pips.value() as usize] -= 1;
+        }
+        let kind = self.keys.piece_kinds[entry.piece_index];
+        self.hash ^= self.keys.pieces[kind][self.kind_uses[kind]];
+        self.kind_uses[kind] += 1;
+        self.cells_remaining -= entry.cell_indices.len();
+        self.constraint_hash = self.keys.constraints_hash(&next_ids, &next);
+        self.hash ^= previous_constraint_hash ^ self.constraint_hash;
+        let previous_constraints = mem::replace(&mut self.constraints, next);
+        let previous_ids = mem::replace(&mut self.constraint_ids, next_ids);
+        self.placements.push(placement);
+        self.nodes += 1;
+        self.undo_log.push(UndoEntry {
+            entry_index,
+            previous_constraints,
+            previous_ids,
+            previous_hash,
+            previous_constraint_hash,
+        });
+    }
+
+    fn undo(&mut self, catalog: &PlacementCatalog) {
+        let Some(undo) = self.undo_log.pop() else {
+            return;
+        };
+        let entry = &catalog.entries[undo.entry_index];
+        if let Some(placement) = self.placements.pop() {
+            for pips in placement.piece.pips() {
+                self.pip_counts[pips.value() as usize] += 1;
+            }
+        }
+        self.constraints = undo.previous_constraints;
+        self.constraint_ids = undo.previous_ids;
+        self.hash = undo.previous_hash;
+        self.constraint_hash = undo.previous_constraint_hash;
+        self.cells_remaining += entry.cell_indices.len();
+        self.used_pieces[entry.piece_index] = false;
+        self.kind_uses[self.keys.piece_kinds[entry.piece_index]] -= 1;
+        for &cell in &entry.cell_indices {
+            self.remaining[cell] = true;
+        }
+    }
+}
+
+/// Everything needed to revert one applied placement.
+struct UndoEntry {
+    entry_index: usize,
+    previous_constraints: Vec<Constraint>,
+    previous_ids: Vec<usize>,
+    previous_hash: u64,
+    previous_constraint_hash: u64,
+}
+
+/// One level of the search: the pivot cell and the next candidate to try there.
+struct Frame {
+    pivot: usize,
+    next_candidate: usize,
+    applied: bool,
+}
+
+impl Frame {
+    fn new(pivot: usize) -> Self {
+        Self {
+            pivot,
+            next_candidate: 0,
+            applied: false,
+        }
+    }
+}
+
+/// Depth-first search driven by an explicit stack of frames rather than recursion, so board
+/// size is bounded by heap rather than thread stack. Each frame owns at most one entry in the
+/// state's undo log, which is reverted before the frame tries its next candidate.
+fn search(game: &Game, catalog: &PlacementCatalog, state: &mut SearchState) -> bool {
+    if state.cells_remaining == 0 {
+        return state.constraints.is_empty();
+    }
+
+    let mut stack = match select_cell(catalog, &state.remaining, &state.used_pieces) {
+        Some(pivot) => vec![Frame::new(pivot)],
         None => return false,
     };
 
-    for &entry_index in &catalog.cell_to_entries[pivot] {
-        let entry = &catalog.entries[entry_index];
-        if used_pieces[entry.piece_index] {
+    while let Some(frame) = stack.last_mut() {
+        if frame.applied {
+            state.undo(catalog);
+            frame.applied = false;
+        }
+
+        if !advance_frame(game, catalog, state, frame) {
+            stack.pop();
             continue;
         }
-        if entry.cell_indices.iter().any(|&cell| !remaining[cell]) {
+        frame.applied = true;
+
+        if state.cells_remaining == 0 {
+            if state.constraints.is_empty() {
+                return true;
+            }
             continue;
         }
+        if let Some(pivot) = select_cell(catalog, &state.remaining, &state.used_pieces) {
+            stack.push(Frame::new(pivot));
+        }
+    }
 
-        let piece = game.pieces[entry.piece_index].clone();
-        let pip_order = piece.pips().to_vec();
-        let placement = Placement::new(piece, entry.anchor, entry.orientation_index, pip_order);
+    false
+}
 
-        let next_constraints = match reduce_constraints(constraints.as_slice(), &placement) {
-            Ok(result) => result,
-            Err(_) => continue,
+/// Applies the frame's next viable candidate placement, returning `false` once the pivot's
+/// candidates are exhausted.
+fn advance_frame(
+    game: &Game,
+    catalog: &PlacementCatalog,
+    state: &mut SearchState,
+    frame: &mut Frame,
+) -> bool {
+    let candidates = &catalog.cell_to_entries[frame.pivot];
+    while frame.next_candidate < candidates.len() {
+        let entry_index = candidates[frame.next_candidate];
+        frame.next_candidate += 1;
+
+        let Some((placement, next_constraints, next_ids)) =
+            try_candidate(game, catalog, state, entry_index)
+        else {
+            continue;
         };
+        let entry = &catalog.entries[entry_index];
+        state.apply(entry_index, entry, placement, next_constraints, next_ids);
+        return true;
+    }
+    false
+}
 
-        for &cell in &entry.cell_indices {
-            remaining[cell] = false;
-        }
-        used_pieces[entry.piece_index] = true;
-        *cells_remaining -= entry.cell_indices.len();
-
-        let previous_constraints = mem::replace(constraints, next_constraints);
-        placements.push(placement);
-
-        if search(
-            game,
-            catalog,
-            remaining,
-            used_pieces,
-            constraints,
-            placements,
-            cells_remaining,
-        ) {
-            return true;
+/// The placement for catalog row `entry_index` and the constraints left after it, or `None` if
+/// the row overlaps covered cells, reuses a piece, or breaks or strands a constraint.
+fn try_candidate(
+    game: &Game,
+    catalog: &PlacementCatalog,
+    state: &SearchState,
+    entry_index: usize,
+) -> Option<(Placement, Vec<Constraint>, Vec<usize>)> {
+    let entry = &catalog.entries[entry_index];
+    if state.used_pieces[entry.piece_index] {
+        return None;
+    }
+    if entry
+        .cell_indices
+        .iter()
+        .any(|&cell| !state.remaining[cell])
+    {
+        return None;
+    }
+
+    let piece = game.pieces[entry.piece_index].clone();
+    let placement = Placement::new(
+        piece,
+        entry.anchor,
+        entry.orientation_index,
+        entry.pip_order.clone(),
+    );
+
+    let (next_constraints, next_ids) = reduce_tracked(state, &placement)?;
+    let mut left = state.pip_counts;
+    for pips in placement.piece.pips() {
+        left[pips.value() as usize] -= 1;
+    }
+    if !sums_reachable(&left, &next_constraints) {
+        return None;
+    }
+    Some((placement, next_constraints, next_ids))
+}
+
+/// Reduces the state's constraints by `placement`, keeping each survivor's original id.
+/// Returns `None` if the placement violates a constraint.
+fn reduce_tracked(
+    state: &SearchState,
+    placement: &Placement,
+) -> Option<(Vec<Constraint>, Vec<usize>)> {
+    let mut constraints = Vec::with_capacity(state.constraints.len());
+    let mut ids = Vec::with_capacity(state.constraints.len());
+    for (constraint, &id) in state.constraints.iter().zip(&state.constraint_ids) {
+        match constraint.reduce_placement(placement) {
+            Ok(Some(next)) => {
+                constraints.push(next);
+                ids.push(id);
+            }
+            Ok(None) => {}
+            Err(_) => return None,
         }
+    }
+    Some((constraints, ids))
+}
 
-        placements.pop();
-        *constraints = previous_constraints;
-        *cells_remaining += entry.cell_indices.len();
-        used_pieces[entry.piece_index] = false;
-        for &cell in &entry.cell_indices {
-            remaining[cell] = true;
+/// Count of pips of each value, indexed by `Pips::value`.
+type PipCounts = [usize; Pips::MAX as usize + 1];
+
+fn pip_counts(pieces: &[Piece]) -> PipCounts {
+    let mut counts = [0; Pips::MAX as usize + 1];
+    for pips in pieces.iter().flat_map(|piece| piece.pips()) {
+        counts[pips.value() as usize] += 1;
+    }
+    counts
+}
+
+/// Sum of the `count` smallest pips in `counts`, or of all of them if there are fewer.
+fn smallest_sum(counts: &PipCounts, count: usize) -> u32 {
+    extreme_sum(counts.iter().enumerate(), count)
+}
+
+/// Sum of the `count` largest pips in `counts`, or of all of them if there are fewer.
+fn largest_sum(counts: &PipCounts, count: usize) -> u32 {
+    extreme_sum(counts.iter().enumerate().rev(), count)
+}
+
+fn extreme_sum<'a>(values: impl Iterator<Item = (usize, &'a usize)>, count: usize) -> u32 {
+    let mut left = count;
+    let mut sum = 0;
+    for (value, &available) in values {
+        let taken = available.min(left);
+        sum += (value * taken) as u32;
+        left -= taken;
+        if left == 0 {
+            break;
         }
     }
+    sum
+}
 
-    false
+/// Whether every open sum region can still meet its (already reduced) target with the pips
+/// left in hand (`left`): the region's `k` cells sum to at least the `k` smallest remaining
+/// pips and at most the `k` largest.
+fn sums_reachable(left: &PipCounts, constraints: &[Constraint]) -> bool {
+    constraints.iter().all(|constraint| {
+        let count = constraint.points().len();
+        match constraint {
+            Constraint::Exactly { target, .. } => {
+                smallest_sum(left, count) <= *target && *target <= largest_sum(left, count)
+            }
+            Constraint::LessThan { target, .. } => smallest_sum(left, count) < *target,
+            Constraint::MoreThan { target, .. } => largest_sum(left, count) > *target,
+            _ => true,
+        }
+    })
 }
 
 fn select_cell(
@@ -263,9 +810,13 @@ fn validate_solution(game: &Game, placements: &[Placement]) -> Result<(), String
 
 #[cfg(test)]
 mod tests {
-    use super::solve;
+    use super::{
+        Frame, PlacementCatalog, SearchOptions, SearchState, advance_frame, count_search_nodes,
+        largest_sum, pip_counts, smallest_sum, solve, solve_counting, solve_with,
+        validate_solution,
+    };
     use crate::loader;
-    use crate::model::{Constraint, Game, Piece, Pips, Point};
+    use crate::model::{Board, Constraint, Game, Piece, Pips, Placement, Point};
     use std::collections::HashSet;
     use std::path::Path;
     use std::sync::Arc;
@@ -303,6 +854,25 @@ mod tests {
         assert_eq!(solution.len(), 1);
     }
 
+    #[test]
+    fn places_domino_reversed_when_constraints_require_it() {
+        let mut points = HashSet::new();
+        points.insert(Point::new(0, 0));
+        points.insert(Point::new(1, 0));
+        let board = Board::new(points);
+        let piece = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let constraint = Constraint::Exactly {
+            target: 2,
+            points: Arc::new([Point::new(0, 0)].into_iter().collect()),
+        };
+        let game = Game::new(board, vec![piece], vec![constraint]);
+        let solution = solve(&game).expect("reversed domino satisfies the constraint");
+        assert_eq!(
+            solution[0].pip_at(Point::new(0, 0)),
+            Some(Pips::new(2).unwrap())
+        );
+    }
+
     #[test]
     fn detects_unsatisfied_constraint_early() {
         let mut points = HashSet::new();
@@ -318,4 +888,285 @@ mod tests {
         game.validate().expect("game should validate");
         assert!(solve(&game).is_err());
     }
+
+    #[test]
+    fn solves_large_domino_board_on_a_small_stack() {
+        // 20x20 rather than 30x30: 200 placements deep already overflowed this 128 KiB stack
+        // when the search recursed per placement, while every identical domino gets its own
+        // catalog rows, so a 30x30 board spends about a minute of a debug build in cell selection.
+        let handle = std::thread::Builder::new()
+            .stack_size(128 * 1024)
+            .spawn(|| {
+                let side = 20u32;
+                let points: HashSet<Point> = (0..side)
+                    .flat_map(|y| (0..side).map(move |x| Point::new(x, y)))
+                    .collect();
+                let board = Board::new(points);
+                let pieces = vec![
+                    Piece::domino(Pips::new(0).unwrap(), Pips::new(0).unwrap());
+                    (side * side / 2) as usize
+                ];
+                let game = Game::new(board, pieces, vec![]);
+                game.validate().expect("game should validate");
+                solve(&game).map(|placements| placements.len())
+            })
+            .expect("spawn solver thread");
+        let placed = handle
+            .join()
+            .expect("solver should not overflow the stack")
+            .expect("large board should solve");
+        assert_eq!(placed, 200);
+    }
+
+    #[test]
+    fn incremental_hash_returns_to_start_after_undo() {
+        let mut points = HashSet::new();
+        points.insert(Point::new(0, 0));
+        points.insert(Point::new(1, 0));
+        let board = Board::new(points.clone());
+        let pieces = vec![Piece::domino(Pips::new(2).unwrap(), Pips::new(3).unwrap())];
+        let constraints = vec![Constraint::Exactly {
+            target: 5,
+            points: Arc::new(points),
+        }];
+        let game = Game::new(board, pieces, constraints);
+        let catalog = PlacementCatalog::from_game(&game).unwrap();
+        let mut state = SearchState::new(&game, &catalog);
+        let start = state.hash();
+
+        let mut frame = Frame::new(0);
+        assert!(advance_frame(&game, &catalog, &mut state, &mut frame));
+        assert!(state.constraints.is_empty());
+        assert_ne!(state.hash(), start);
+        // Every cell and the only constraint are gone; only the used piece is left in the hash.
+        assert_eq!(state.hash(), state.keys.pieces[0][0]);
+
+        state.undo(&catalog);
+        assert_eq!(state.hash(), start);
+    }
+
+    #[test]
+    fn rejects_board_area_not_divisible_by_piece_sizes() {
+        let board = Board::new(
+            [(0, 0), (1, 0), (2, 0)]
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y))
+                .collect(),
+        );
+        let domino = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let game = Game::new(board, vec![domino.clone(), domino], vec![]);
+        let err = solve(&game).unwrap_err();
+        assert!(
+            err.contains("3 cells") && err.contains("multiple of 2"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn rejects_all_different_region_without_enough_distinct_pips() {
+        let cells = [(0, 0), (1, 0), (0, 1), (1, 1)].map(|(x, y)| Point::new(x, y));
+        let board = Board::new(cells.into_iter().collect());
+        let domino = Piece::domino(Pips::new(1).unwrap(), Pips::new(2).unwrap());
+        let constraint = Constraint::AllDifferent {
+            excluded: Arc::new(HashSet::new()),
+            points: Arc::new(cells[..3].iter().copied().collect()),
+        };
+        let game = Game::new(board, vec![domino.clone(), domino], vec![constraint]);
+        game.validate().expect("game should validate");
+        let err = solve(&game).unwrap_err();
+        assert!(
+            err.contains("needs 3 distinct") && err.contains("only offer 2"),
+            "{}",
+            err
+        );
+    }
+
+    fn two_component_game(island_target: u32) -> Game {
+        // A 1x2 island at the origin and a 4x4 block to its right, with a gap between.
+        let mut cells = vec![Point::new(0, 0), Point::new(1, 0)];
+        cells.extend((0..4).flat_map(|y| (3..7).map(move |x| Point::new(x, y))));
+        let board = Board::new(cells.into_iter().collect());
+        let pieces: Vec<Piece> = (0..9)
+            .map(|i| Piece::domino(Pips::new(i % 3).unwrap(), Pips::new((i + 1) % 3).unwrap()))
+            .collect();
+        let constraint = Constraint::Exactly {
+            target: island_target,
+            points: Arc::new([Point::new(0, 0), Point::new(1, 0)].into_iter().collect()),
+        };
+        Game::new(board, pieces, vec![constraint])
+    }
+
+    #[test]
+    fn sequential_components_solve_each_component_in_turn() {
+        let game = two_component_game(3);
+        game.validate().expect("game should validate");
+        let options = SearchOptions {
+            sequential_components: true,
+        };
+        let placements = solve_with(&game, &options).expect("solvable");
+        validate_solution(&game, &placements).expect("valid solution");
+    }
+
+    #[test]
+    fn sequential_components_fall_back_to_joint_search_for_straddling_constraints() {
+        let mut game = two_component_game(3);
+        game.constraints.push(Constraint::Exactly {
+            target: 2,
+            points: Arc::new([Point::new(0, 0), Point::new(3, 0)].into_iter().collect()),
+        });
+        assert!(game.partition_components().is_err());
+        let options = SearchOptions {
+            sequential_components: true,
+        };
+        let placements = solve_with(&game, &options).expect("joint search solves it");
+        validate_solution(&game, &placements).expect("valid solution");
+    }
+
+    #[test]
+    fn sequential_components_fail_on_the_unsolvable_component() {
+        // A 4x4 block with a 2x2 island to its right. Two dominoes sum to at most 3 + 3, so the
+        // island's target of 7 is out of reach, though no single cell's pips rule it out.
+        let mut cells: Vec<Point> = (0..4)
+            .flat_map(|y| (0..4).map(move |x| Point::new(x, y)))
+            .collect();
+        let island: Vec<Point> = (0..2)
+            .flat_map(|y| (5..7).map(move |x| Point::new(x, y)))
+            .collect();
+        cells.extend(&island);
+        let board = Board::new(cells.into_iter().collect());
+        let pieces: Vec<Piece> = (0..10)
+            .map(|i| Piece::domino(Pips::new(i % 3).unwrap(), Pips::new((i + 1) % 3).unwrap()))
+            .collect();
+        let constraint = Constraint::Exactly {
+            target: 7,
+            points: Arc::new(island.into_iter().collect()),
+        };
+        let game = Game::new(board, pieces, vec![constraint]);
+        game.validate().expect("game should validate");
+        let options = SearchOptions {
+            sequential_components: true,
+        };
+        let mut nodes = 0;
+        let err = solve_counting(&game, &options, &mut nodes).unwrap_err();
+        assert!(
+            err.contains("containing (5, 0) (4 cells) cannot be solved"),
+            "{}",
+            err
+        );
+        // The joint search keeps retiling the block before every failed attempt at the island.
+        let joint = count_search_nodes(&game).expect("catalog builds");
+        assert!(
+            nodes * 10 < joint,
+            "sequential {} vs joint {}",
+            nodes,
+            joint
+        );
+    }
+
+    #[test]
+    fn rejects_all_same_region_without_enough_matching_pips() {
+        let cells = [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)].map(|(x, y)| Point::new(x, y));
+        let board = Board::new(cells.into_iter().collect());
+        let pieces = vec![
+            Piece::domino(Pips::new(5).unwrap(), Pips::new(1).unwrap()),
+            Piece::domino(Pips::new(5).unwrap(), Pips::new(2).unwrap()),
+            Piece::domino(Pips::new(3).unwrap(), Pips::new(4).unwrap()),
+        ];
+        let constraint = Constraint::AllSame {
+            expected: Some(Pips::new(5).unwrap()),
+            points: Arc::new(cells[..3].iter().copied().collect()),
+        };
+        let game = Game::new(board, pieces, vec![constraint]);
+        game.validate().expect("game should validate");
+        let err = PlacementCatalog::from_game(&game)
+            .err()
+            .expect("pool is short of fives");
+        assert!(
+            err.contains("needs 3 pips of value 5") && err.contains("only offer 2"),
+            "{}",
+            err
+        );
+        assert_eq!(solve(&game).unwrap_err(), err);
+    }
+
+    #[test]
+    fn prunes_placement_that_leaves_more_than_region_unreachable() {
+        let cells = [(0, 0), (1, 0), (2, 0), (3, 0)].map(|(x, y)| Point::new(x, y));
+        let board = Board::new(cells.into_iter().collect());
+        let low = Piece::domino(Pips::new(1).unwrap(), Pips::new(1).unwrap());
+        let high = Piece::domino(Pips::new(4).unwrap(), Pips::new(4).unwrap());
+        let constraint = Constraint::MoreThan {
+            target: 7,
+            points: Arc::new(cells[2..].iter().copied().collect()),
+        };
+        let game = Game::new(board, vec![high, low], vec![constraint]);
+        let catalog = PlacementCatalog::from_game(&game).unwrap();
+        let mut state = SearchState::new(&game, &catalog);
+
+        // Putting the 4-4 on the left leaves only the 1-1 for a region that needs more than 7.
+        let pivot = catalog
+            .board_points
+            .iter()
+            .position(|&point| point == cells[0])
+            .unwrap();
+        let mut frame = Frame::new(pivot);
+        while advance_frame(&game, &catalog, &mut state, &mut frame) {
+            assert_eq!(
+                state.placements[0].piece.pips()[0],
+                Pips::new(1).unwrap(),
+                "4-4 next to the region should have been pruned"
+            );
+            state.undo(&catalog);
+        }
+
+        let solution = solve(&game).expect("1-1 left, 4-4 right solves");
+        assert_eq!(solution.len(), 2);
+        assert_eq!(count_search_nodes(&game).unwrap(), 2);
+    }
+
+    #[test]
+    fn hash_tells_apart_states_that_used_different_pieces() {
+        let cells = [(0, 0), (1, 0)].map(|(x, y)| Point::new(x, y));
+        let board = Board::new(cells.into_iter().collect());
+        let domino = |a, b| Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap());
+        let game = Game::new(
+            board,
+            vec![domino(1, 2), domino(5, 6), domino(1, 2)],
+            vec![],
+        );
+        let catalog = PlacementCatalog::from_game(&game).unwrap();
+        let hash_after = |piece_index: usize| {
+            let mut state = SearchState::new(&game, &catalog);
+            let entry_index = (0..catalog.entries.len())
+                .find(|&index| catalog.entries[index].piece_index == piece_index)
+                .unwrap();
+            let entry = &catalog.entries[entry_index];
+            let placement = Placement::new(
+                game.pieces[piece_index].clone(),
+                entry.anchor,
+                entry.orientation_index,
+                entry.pip_order.clone(),
+            );
+            state.apply(entry_index, entry, placement, Vec::new(), Vec::new());
+            state.hash()
+        };
+
+        // Same cells covered either way; only the piece left in hand differs.
+        assert_ne!(hash_after(0), hash_after(1));
+        // Identical pieces are interchangeable, so using either copy is the same state.
+        assert_eq!(hash_after(0), hash_after(2));
+    }
+
+    #[test]
+    fn pip_histogram_sums_take_the_extremes() {
+        let domino = |a, b| Piece::domino(Pips::new(a).unwrap(), Pips::new(b).unwrap());
+        let counts = pip_counts(&[domino(1, 5), domino(1, 6), domino(3, 3)]);
+        assert_eq!(counts, [0, 2, 0, 2, 0, 1, 1]);
+        assert_eq!(smallest_sum(&counts, 3), 1 + 1 + 3);
+        assert_eq!(largest_sum(&counts, 3), 6 + 5 + 3);
+        // Asking for more cells than there are pips sums whatever is left.
+        assert_eq!(smallest_sum(&counts, 10), 19);
+        assert_eq!(largest_sum(&counts, 0), 0);
+    }
 }
@@ -0,0 +1,23 @@
+/// Greatest common divisor; `gcd(0, n)` is `n`, so it folds from 0 over a list.
+pub fn gcd(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gcd;
+
+    #[test]
+    fn gcd_handles_zero_and_coprime_inputs() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 6), 6);
+        assert_eq!(gcd(6, 0), 6);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(5, 3), 1);
+        assert_eq!([4, 6, 10].into_iter().fold(0, gcd), 2);
+    }
+}
@@ -34,6 +34,46 @@ impl SimpleRng {
         (self.next_u64() % (span + 1)) as usize + min
     }
 
+    /// Returns `true` with probability `p` (clamped to `[0, 1]`).
+    ///
+    /// The generator is a power-of-two-modulus LCG, so its low bits cycle with short periods
+    /// (bit 0 simply alternates). The helpers below draw from the high bits instead; the
+    /// `%`-based range helpers above inherit that low-bit bias.
+    pub fn gen_bool(&mut self, p: f64) -> bool {
+        if p >= 1.0 {
+            return true;
+        }
+        if p <= 0.0 || p.is_nan() {
+            return false;
+        }
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    /// Picks an index with probability proportional to its weight. Zero weights are never
+    /// chosen. Panics if every weight is zero.
+    pub fn weighted_index(&mut self, weights: &[u32]) -> usize {
+        let total: u64 = weights.iter().map(|&weight| weight as u64).sum();
+        assert!(
+            total > 0,
+            "weighted_index needs at least one positive weight"
+        );
+        let mut roll = ((self.next_u64() as u128 * total as u128) >> 64) as u64;
+        for (index, &weight) in weights.iter().enumerate() {
+            if roll < weight as u64 {
+                return index;
+            }
+            roll -= weight as u64;
+        }
+        unreachable!("roll is always below the total weight")
+    }
+
+    /// Uniform draw from `0..n`. Panics if `n` is zero.
+    pub fn gen_below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "gen_below needs a non-empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
     pub fn shuffle<T>(&mut self, slice: &mut [T]) {
         for i in (1..slice.len()).rev() {
             let j = (self.next_u64() % (i as u64 + 1)) as usize;
@@ -41,3 +81,47 @@ impl SimpleRng {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SimpleRng;
+
+    #[test]
+    fn gen_bool_respects_certain_probabilities() {
+        let mut rng = SimpleRng::new(Some(42), 0, 0);
+        for _ in 0..1000 {
+            assert!(rng.gen_bool(1.0));
+            assert!(!rng.gen_bool(0.0));
+        }
+    }
+
+    #[test]
+    fn weighted_index_never_picks_zero_weights() {
+        let mut rng = SimpleRng::new(Some(42), 0, 0);
+        for _ in 0..1000 {
+            assert_eq!(rng.weighted_index(&[0, 1, 0]), 1);
+        }
+    }
+
+    #[test]
+    fn weighted_index_reaches_every_positive_weight() {
+        let mut rng = SimpleRng::new(Some(7), 0, 0);
+        let mut seen = [0usize; 3];
+        for _ in 0..1000 {
+            seen[rng.weighted_index(&[1, 2, 1])] += 1;
+        }
+        assert!(seen.iter().all(|&count| count > 0));
+        assert!(seen[1] > seen[0] && seen[1] > seen[2]);
+    }
+
+    #[test]
+    fn gen_below_covers_the_whole_range() {
+        let mut rng = SimpleRng::new(Some(3), 0, 0);
+        let mut seen = [0usize; 5];
+        for _ in 0..1000 {
+            seen[rng.gen_below(5)] += 1;
+        }
+        assert!(seen.iter().all(|&count| count > 0));
+        assert_eq!(rng.gen_below(1), 0);
+    }
+}
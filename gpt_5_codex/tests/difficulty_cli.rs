@@ -0,0 +1,67 @@
+use std::fs;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const EASY: &str = "board:
+##
+
+pieces:
+12
+
+constraints:
+Exactly 1 {(0,0)}
+";
+
+const WIDER: &str = "board:
+####
+####
+
+pieces:
+12,34,55,60
+
+constraints:
+AllSame None {(2,0),(3,0)}
+";
+
+#[test]
+fn reports_one_csv_row_per_puzzle() {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time ok")
+        .as_nanos();
+    let dir = std::env::temp_dir().join(format!("pips_difficulty_{}", timestamp));
+    fs::create_dir(&dir).expect("create temp dir");
+    fs::write(dir.join("a-easy.txt"), EASY).expect("write fixture");
+    fs::write(dir.join("b-wider.txt"), WIDER).expect("write fixture");
+
+    let binary = env!("CARGO_BIN_EXE_difficulty");
+    let output = Command::new(binary)
+        .arg(&dir)
+        .output()
+        .expect("failed to spawn difficulty");
+    fs::remove_dir_all(&dir).ok();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "difficulty exited with {:?}\nstdout:\n{}\nstderr:\n{}",
+        output.status.code(),
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "filename,cells,pieces,constraint_density,nodes,difficulty"
+    );
+    let rows: Vec<Vec<&str>> = lines[1..]
+        .iter()
+        .map(|line| line.split(',').collect())
+        .collect();
+    assert_eq!(rows.len(), 2, "expected two data rows:\n{}", stdout);
+    assert_eq!(rows[0][..4], ["a-easy.txt", "2", "1", "0.500"]);
+    assert_eq!(rows[0][5], "easy");
+    assert_eq!(rows[1][..4], ["b-wider.txt", "8", "4", "0.250"]);
+    assert!(rows[1][4].parse::<usize>().expect("numeric node count") >= 4);
+}
@@ -0,0 +1,34 @@
+use std::process::Command;
+
+#[test]
+fn constraint_mode_draws_the_label() {
+    let binary = env!("CARGO_BIN_EXE_draw_polypips");
+    let output = Command::new(binary)
+        .arg("--constraint")
+        .arg("Exactly:7:(0,0),(1,0)")
+        .output()
+        .expect("failed to spawn draw_polypips");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "stdout:\n{}", stdout);
+    assert!(lines[1].contains('7'), "stdout:\n{}", stdout);
+}
+
+#[test]
+fn constraint_mode_rejects_tokens_without_points() {
+    let binary = env!("CARGO_BIN_EXE_draw_polypips");
+    let output = Command::new(binary)
+        .arg("--constraint")
+        .arg("Exactly:7")
+        .output()
+        .expect("failed to spawn draw_polypips");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("kind:argument:points"));
+}
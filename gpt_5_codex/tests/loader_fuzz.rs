@@ -0,0 +1,75 @@
+use pips_solver::loader;
+use pips_solver::util::rng::SimpleRng;
+use std::io::Cursor;
+
+const SEED_GAME: &str = "// fuzz seed
+board:
+####
+####
+
+pieces:
+12,34,4I:1234
+
+constraints:
+Exactly 3 {(0,0),(1,0)}
+AllDifferent {} {(2,0),(3,0)}
+AllSame Some(4) {(0,1)}
+LessThan 10 {(1,1),(2,1),(3,1)}
+";
+
+/// Characters that matter to the grammar, plus multibyte ones that break byte slicing.
+const ALPHABET: &[char] = &[
+    '#', ' ', '\n', '(', ')', '{', '}', ',', ':', '+', '-', '0', '1', '4', '9', 'I', 'L', 'S', 'é',
+    'ü', '€', '😀', '\u{301}',
+];
+
+fn mutate(rng: &mut SimpleRng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..=rng.gen_below(4) {
+        let ch = ALPHABET[rng.gen_below(ALPHABET.len())];
+        let at = rng.gen_below(chars.len() + 1);
+        match rng.gen_below(3) {
+            0 if at < chars.len() => {
+                chars.remove(at);
+            }
+            1 if at < chars.len() => chars[at] = ch,
+            _ => chars.insert(at, ch),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+fn load(input: &str) {
+    // Errors are fine; the loader must simply never panic.
+    let _ = loader::load_game_from_reader(Cursor::new(input.as_bytes()));
+}
+
+#[test]
+fn seed_game_loads() {
+    loader::load_game_from_reader(Cursor::new(SEED_GAME.as_bytes())).expect("seed is valid");
+}
+
+#[test]
+fn loader_never_panics_on_mutated_games() {
+    let mut rng = SimpleRng::new(Some(0x5eed), 0, 0);
+    for _ in 0..5_000 {
+        load(&mutate(&mut rng, SEED_GAME));
+    }
+}
+
+#[test]
+fn loader_never_panics_on_random_text() {
+    let mut rng = SimpleRng::new(Some(0xf022), 0, 0);
+    for _ in 0..2_000 {
+        let len = rng.gen_below(80);
+        let noise: String = (0..len)
+            .map(|_| ALPHABET[rng.gen_below(ALPHABET.len())])
+            .collect();
+        load(&noise);
+        load(&format!("board:\n##\npieces:\n{}\n", noise));
+        load(&format!(
+            "board:\n##\npieces:\n12\nconstraints:\nExactly 3 {}\n",
+            noise
+        ));
+    }
+}
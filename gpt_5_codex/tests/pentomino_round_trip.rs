@@ -0,0 +1,60 @@
+use pips_solver::{
+    model::{Board, Point, PolyShape},
+    polypips::{
+        config::{GeneratorConfig, TilingOrder},
+        generator::generate,
+        rules::{ConstraintRule, ConstraintSelection, PieceRule},
+    },
+    solver_v2,
+};
+use std::collections::{HashMap, HashSet};
+
+fn grid(width: u32, height: u32) -> Board {
+    let mut points = HashSet::new();
+    for y in 0..height {
+        for x in 0..width {
+            points.insert(Point::new(x, y));
+        }
+    }
+    Board::new(points)
+}
+
+#[test]
+fn generated_pentomino_set_solves_back() {
+    for seed in [7, 2414] {
+        let config = GeneratorConfig {
+            board: grid(12, 5),
+            piece_rule: PieceRule::ExactPentominoSet,
+            constraint_rule: ConstraintRule::Allowed(vec![
+                PolyShape::Mono,
+                PolyShape::Domino,
+                PolyShape::TriI,
+                PolyShape::TriL,
+            ]),
+            coverage: 0.6,
+            selection: ConstraintSelection::UniformAll,
+            seed: Some(seed),
+            fixed_cells: HashMap::new(),
+            tiling_order: TilingOrder::InteriorFirst,
+        };
+        let puzzle = generate(config).expect("12x5 pentomino puzzle generates");
+        assert_eq!(puzzle.pieces.len(), 12);
+
+        let game = puzzle.as_game();
+        game.validate().expect("generated game is valid");
+        assert!(
+            game.apply_placements(&puzzle.placements)
+                .expect("generator's own placements replay")
+                .is_won(),
+            "seed {}: generator's placements do not win",
+            seed
+        );
+
+        let solution = solver_v2::solve(&game)
+            .unwrap_or_else(|err| panic!("seed {}: solver failed: {}", seed, err));
+        let finished = game
+            .apply_placements(&solution)
+            .unwrap_or_else(|err| panic!("seed {}: solution does not replay: {}", seed, err));
+        assert!(finished.is_won(), "seed {}: solution leaves work", seed);
+    }
+}
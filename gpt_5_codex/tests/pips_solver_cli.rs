@@ -0,0 +1,185 @@
+use std::process::Command;
+
+#[test]
+fn tiny_timeout_exits_with_timeout_code() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--timeout")
+        .arg("0.01")
+        .arg("../examples/game-2025-08-18-hard.txt")
+        .output()
+        .expect("failed to spawn pips-solver");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "expected timeout exit code\nstdout:\n{}\nstderr:\n{}",
+        stdout,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("Timed out after 0.01s"),
+        "stdout missing timeout message:\n{}",
+        stdout
+    );
+}
+
+#[test]
+fn validate_only_reports_counts_without_solving() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--validate-only")
+        .arg("../poly_games/2x2.txt")
+        .output()
+        .expect("failed to spawn pips-solver");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout:\n{}", stdout);
+    assert_eq!(
+        stdout.lines().collect::<Vec<_>>(),
+        ["OK", "4 cells, 2 pieces"]
+    );
+}
+
+#[test]
+fn validate_only_rejects_malformed_constraint() {
+    let path = std::env::temp_dir().join(format!("pips_validate_only_{}.txt", std::process::id()));
+    std::fs::write(
+        &path,
+        "board:\n##\n\npieces:\n12\n\nconstraints:\nExactly many {(0,0)}\n",
+    )
+    .expect("write fixture");
+
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--validate-only")
+        .arg(&path)
+        .output()
+        .expect("failed to spawn pips-solver");
+    std::fs::remove_file(&path).ok();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "stderr:\n{}", stderr);
+    assert!(stderr.contains("Exactly target"), "stderr:\n{}", stderr);
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("OK"));
+}
+
+#[test]
+fn progress_reports_on_stderr_only() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--progress")
+        .arg("../poly_games/2x5.txt")
+        .output()
+        .expect("failed to spawn pips-solver");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr:\n{}", stderr);
+    assert!(stderr.contains("nodes explored"), "stderr:\n{}", stderr);
+    assert!(!stdout.contains("nodes explored"), "stdout:\n{}", stdout);
+    assert!(stdout.contains("Found a solution"));
+}
+
+#[test]
+fn repeat_reports_timing_stats() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--repeat")
+        .arg("3")
+        .arg("../poly_games/2x5.txt")
+        .output()
+        .expect("failed to spawn pips-solver");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("Timing over 3 runs:"),
+        "stdout:\n{}",
+        stdout
+    );
+    for label in ["min:", "median:", "max:"] {
+        assert!(stdout.contains(label), "stdout:\n{}", stdout);
+    }
+    assert_eq!(stdout.matches("Found a solution").count(), 1);
+}
+
+#[test]
+fn repeat_rejects_zero_runs() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let output = Command::new(binary)
+        .arg("--repeat")
+        .arg("0")
+        .arg("../poly_games/2x5.txt")
+        .output()
+        .expect("failed to spawn pips-solver");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid --repeat value"));
+}
+
+#[test]
+fn drop_trivial_warns_on_stderr_and_solves() {
+    let path = std::env::temp_dir().join(format!("pips_drop_trivial_{}.txt", std::process::id()));
+    std::fs::write(
+        &path,
+        "board:\n##\n\npieces:\n12\n\nconstraints:\nLessThan 20 {(0,0),(1,0)}\nExactly 1 {(0,0)}\n",
+    )
+    .expect("write fixture");
+
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    let strict = Command::new(binary)
+        .arg(&path)
+        .output()
+        .expect("failed to spawn pips-solver");
+    let output = Command::new(binary)
+        .arg("--drop-trivial")
+        .arg(&path)
+        .output()
+        .expect("failed to spawn pips-solver");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(strict.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr:\n{}", stderr);
+    assert!(
+        stderr.contains("Warning: dropping always-satisfied constraint"),
+        "stderr:\n{}",
+        stderr
+    );
+    assert!(!stdout.contains("Warning"), "stdout:\n{}", stdout);
+    assert!(stdout.contains("Found a solution"), "stdout:\n{}", stdout);
+}
+
+#[test]
+fn huge_timeout_is_a_usage_error_not_a_panic() {
+    let binary = env!("CARGO_BIN_EXE_pips-solver");
+    for value in ["1e30", "-1", "NaN"] {
+        let output = Command::new(binary)
+            .arg("--timeout")
+            .arg(value)
+            .arg("../poly_games/2x2.txt")
+            .output()
+            .expect("failed to spawn pips-solver");
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(
+            output.status.code(),
+            Some(1),
+            "{}: stderr:\n{}",
+            value,
+            stderr
+        );
+        assert!(
+            stderr.contains(&format!("Invalid --timeout value '{}'", value)),
+            "stderr:\n{}",
+            stderr
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use pips_solver::{
+    loader,
+    model::{Game, Placement, Point},
+    solver, solver_v2,
+};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const FIXTURES: &[&str] = &[
+    "poly_games/2x2.txt",
+    "poly_games/2x5.txt",
+    "poly_games/3x3.txt",
+    "poly_games/4x4.txt",
+    "poly_games/6x5.txt",
+    "poly_games/8x8_minus4.txt",
+    "poly_games/constraints/domino_avg.txt",
+];
+
+fn load_game(relative: &str) -> Game {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(relative);
+    loader::load_game_from_path(&path).expect("load test game")
+}
+
+fn assert_wins(game: &Game, placements: &[Placement], label: &str) {
+    let finished = game
+        .apply_placements(placements)
+        .unwrap_or_else(|err| panic!("{}: solution does not replay: {}", label, err));
+    assert!(finished.is_won(), "{}: solution leaves work", label);
+}
+
+#[test]
+fn both_solvers_solve_every_fixture() {
+    for fixture in FIXTURES {
+        let game = load_game(fixture);
+        let exact_cover = solver::solve(&game)
+            .unwrap_or_else(|err| panic!("{}: solver failed: {}", fixture, err));
+        assert_wins(&game, &exact_cover, &format!("{} (solver)", fixture));
+        let backtracking = solver_v2::solve(&game)
+            .unwrap_or_else(|err| panic!("{}: solver_v2 failed: {}", fixture, err));
+        assert_wins(&game, &backtracking, &format!("{} (solver_v2)", fixture));
+    }
+}
+
+#[test]
+fn both_solvers_reject_impossible_fixtures() {
+    // domino_sum asks for 10 from two cells whose pips add up to at most 5.
+    for fixture in [
+        "poly_games/constraints/domino_impossible.txt",
+        "poly_games/constraints/domino_sum.txt",
+    ] {
+        let game = load_game(fixture);
+        assert!(solver::solve(&game).is_err(), "{}: solver", fixture);
+        assert!(solver_v2::solve(&game).is_err(), "{}: solver_v2", fixture);
+    }
+}
+
+/// A solution as each placement's `(y, x, pip)` cells, independent of anchors, orientation
+/// indices and placement order.
+type CanonicalSolution = Vec<Vec<(u32, u32, u8)>>;
+
+fn canonical(placements: &[Placement]) -> CanonicalSolution {
+    let mut pieces: CanonicalSolution = placements
+        .iter()
+        .map(|placement| {
+            let mut cells: Vec<(u32, u32, u8)> = placement
+                .assignments()
+                .iter()
+                .map(|assignment| {
+                    (
+                        assignment.point.y,
+                        assignment.point.x,
+                        assignment.pips.value(),
+                    )
+                })
+                .collect();
+            cells.sort();
+            cells
+        })
+        .collect();
+    pieces.sort();
+    pieces
+}
+
+/// Every solution by brute force: cover the first open cell in row-major order with every
+/// remaining piece in every orientation, anchor and pip order, checked by `apply_placements`.
+fn brute_force(state: &Game, placed: &mut Vec<Placement>, found: &mut BTreeSet<CanonicalSolution>) {
+    if state.is_won() {
+        found.insert(canonical(placed));
+        return;
+    }
+    let Some(target) = state.board.iter().min_by_key(|point| (point.y, point.x)) else {
+        return;
+    };
+    for piece in &state.pieces {
+        for (orientation_index, offsets) in piece.orientations().iter().enumerate() {
+            for &(dx, dy) in offsets {
+                let (Some(x), Some(y)) = (
+                    target.x.checked_add_signed(-dx),
+                    target.y.checked_add_signed(-dy),
+                ) else {
+                    continue;
+                };
+                for pips in piece.pip_permutations() {
+                    let placement =
+                        Placement::new(piece.clone(), Point::new(x, y), orientation_index, pips);
+                    if let Ok(next) = state.apply_placements(std::slice::from_ref(&placement)) {
+                        placed.push(placement);
+                        brute_force(&next, placed, found);
+                        placed.pop();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn enumerated_solutions_match_brute_force() {
+    for fixture in [
+        "poly_games/2x2.txt",
+        "poly_games/2x5.txt",
+        "poly_games/constraints/domino_avg.txt",
+    ] {
+        let game = load_game(fixture);
+        let mut expected = BTreeSet::new();
+        brute_force(&game, &mut Vec::new(), &mut expected);
+        assert!(!expected.is_empty(), "{}", fixture);
+
+        let solutions = solver::find_solutions(&game, usize::MAX).expect("enumerate solutions");
+        for solution in &solutions {
+            assert_wins(&game, solution, fixture);
+        }
+        let enumerated: BTreeSet<CanonicalSolution> = solutions
+            .iter()
+            .map(|solution| canonical(solution))
+            .collect();
+        assert_eq!(enumerated, expected, "{}", fixture);
+        assert!(
+            expected.contains(&canonical(&solver_v2::solve(&game).unwrap())),
+            "{}: solver_v2 solution missing from brute force",
+            fixture
+        );
+    }
+}